@@ -0,0 +1,424 @@
+use {
+    crate::*,
+    rand::seq::SliceRandom,
+    std::{
+        error::Error,
+        io::{BufRead, BufReader, Write},
+        process::{Child, ChildStdin, ChildStdout, Command as Process, Stdio},
+        thread,
+        time::Duration,
+    },
+};
+
+pub type EngineResult<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Chooses the moves of a bot.
+pub trait Engine {
+    fn get_best_move(
+        &mut self,
+        game: &sol_chess::Game,
+        color: sol_chess::Color,
+    ) -> EngineResult<(sol_chess::Square, sol_chess::Square)>;
+}
+
+/// Plays a uniformly random legal move, enough to exercise a game end to end.
+pub struct RandomMover;
+
+impl Engine for RandomMover {
+    fn get_best_move(
+        &mut self,
+        game: &sol_chess::Game,
+        color: sol_chess::Color,
+    ) -> EngineResult<(sol_chess::Square, sol_chess::Square)> {
+        game.get_legal_moves(color)
+            .choose(&mut rand::thread_rng())
+            .copied()
+            .ok_or_else(|| "no legal move".into())
+    }
+}
+
+/// The search built into `sol_chess`, thinking for a fixed time per move.
+pub struct SearchEngine {
+    pub limits: sol_chess::SearchLimits,
+}
+
+impl Engine for SearchEngine {
+    fn get_best_move(
+        &mut self,
+        game: &sol_chess::Game,
+        _color: sol_chess::Color,
+    ) -> EngineResult<(sol_chess::Square, sol_chess::Square)> {
+        sol_chess::get_hint(game, self.limits)?.ok_or_else(|| "no legal move".into())
+    }
+}
+
+/// External engine speaking UCI over its stdin and stdout.
+pub struct UciEngine {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl UciEngine {
+    pub fn spawn(path: &str) -> EngineResult<Self> {
+        let mut process = Process::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("failed to start {}: {}", path, err))?;
+        let stdin = process.stdin.take().ok_or("engine stdin unavailable")?;
+        let stdout = BufReader::new(process.stdout.take().ok_or("engine stdout unavailable")?);
+
+        let mut engine = Self {
+            process,
+            stdin,
+            stdout,
+        };
+        engine.send("uci")?;
+        engine.wait_for("uciok")?;
+        engine.send("ucinewgame")?;
+        engine.send("isready")?;
+        engine.wait_for("readyok")?;
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> EngineResult<()> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    // returns the first line starting with `prefix`, skipping info lines
+    fn wait_for(&mut self, prefix: &str) -> EngineResult<String> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.stdout.read_line(&mut line)? == 0 {
+                return Err("engine exited".into());
+            }
+            if line.starts_with(prefix) {
+                return Ok(line.trim().to_string());
+            }
+        }
+    }
+}
+
+impl Engine for UciEngine {
+    fn get_best_move(
+        &mut self,
+        game: &sol_chess::Game,
+        color: sol_chess::Color,
+    ) -> EngineResult<(sol_chess::Square, sol_chess::Square)> {
+        let (white_clock, black_clock) = get_clocks(game, get_current_timestamp());
+        let increment = game.time_control.increment as u64 * 1000;
+        // pawns always queen on chain, so underpromotions are left out of the
+        // moves the engine may choose from
+        let search_moves: Vec<String> = game
+            .get_legal_moves(color)
+            .into_iter()
+            .map(|(from, to)| to_engine_move(game, from, to))
+            .collect();
+
+        self.send(&format!("position fen {}", to_fen(game)))?;
+        self.send(&format!(
+            "go wtime {} btime {} winc {} binc {} searchmoves {}",
+            white_clock as u64 * 1000,
+            black_clock as u64 * 1000,
+            increment,
+            increment,
+            search_moves.join(" ")
+        ))?;
+
+        let best_move = self.wait_for("bestmove")?;
+        let uci_move = best_move
+            .split_whitespace()
+            .nth(1)
+            .ok_or("engine sent an empty bestmove")?;
+        if let Some(promotion) = uci_move.get(4..).filter(|letter| *letter != "q") {
+            return Err(format!(
+                "engine underpromoted to {} in {}, only queen promotions can be played",
+                promotion, uci_move
+            )
+            .into());
+        }
+        parse_uci_move(uci_move)
+            .ok_or_else(|| format!("engine sent an invalid move {}", uci_move).into())
+    }
+}
+
+// UCI move with the queen letter on promotions
+fn to_engine_move(
+    game: &sol_chess::Game,
+    from: sol_chess::Square,
+    to: sol_chess::Square,
+) -> String {
+    let mut uci_move = to_uci_move(from, to);
+    if game.board.get_piece(from).is_pawn() && (to.rank == 0 || to.rank == 7) {
+        uci_move.push('q');
+    }
+    uci_move
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        self.send("quit").ok();
+        self.process.wait().ok();
+    }
+}
+
+/// Plays `game` with `engine` until it is over, polling the game account every
+/// `refresh` seconds while waiting for the opponent. An opponent who flags is
+/// settled with the timer and one who stops moving is claimed abandoned.
+pub fn run_bot(
+    client: &Client,
+    game: Pubkey,
+    engine: &mut dyn Engine,
+    refresh: u64,
+) -> CommandResult {
+    loop {
+        let game_account = sol_chess_sdk::fetch_game(client, game)?;
+        if game_account.is_finished() {
+            println!("game over: {:?}", game_account.game_state);
+            return Ok(());
+        }
+
+        let color = get_player_color(client, game, &game_account)?;
+        if game_account.get_current_player_color().ok() == Some(color) {
+            let (from, to) = engine.get_best_move(&game_account, color)?;
+            println!("{}", game_account.get_san(color, from, to));
+            move_piece(client, game, &game_account, from, to)?;
+            continue;
+        }
+
+        let current_timestamp = get_current_timestamp();
+        let claimed = if !game_account.is_still_going() {
+            Ok(())
+        } else if get_adversary_clock(&game_account, color, current_timestamp) == 0 {
+            check_timer(client, game, &game_account)
+        } else if game_account.is_inactive(current_timestamp) {
+            let claim_win = game_account.ply >= sol_chess::ABANDONMENT_MIN_PLY;
+            claim_abandonment(client, game, &game_account, claim_win)
+        } else {
+            Ok(())
+        };
+        // the cluster clock can lag behind ours, the claim is retried on the
+        // next refresh
+        if let Err(err) = claimed {
+            println!("claim failed: {}", err);
+        }
+
+        thread::sleep(Duration::from_secs(refresh));
+    }
+}
+
+fn get_adversary_clock(
+    game: &sol_chess::Game,
+    color: sol_chess::Color,
+    current_timestamp: i64,
+) -> u32 {
+    let (white_clock, black_clock) = get_clocks(game, current_timestamp);
+    if color.is_white() {
+        black_clock
+    } else {
+        white_clock
+    }
+}
+
+pub fn bot(client: &Client, args: BotArgs) -> CommandResult {
+    let BotArgs {
+        game,
+        engine,
+        builtin,
+        think_time,
+        refresh,
+    } = args;
+    match engine {
+        Some(path) => run_bot(client, game, &mut UciEngine::spawn(&path)?, refresh),
+        None if builtin => {
+            let mut engine = SearchEngine {
+                limits: get_search_limits(think_time),
+            };
+            run_bot(client, game, &mut engine, refresh)
+        }
+        None => run_bot(client, game, &mut RandomMover, refresh),
+    }
+}
+
+pub fn get_search_limits(think_time: u64) -> sol_chess::SearchLimits {
+    sol_chess::SearchLimits {
+        time_limit: Duration::from_millis(think_time),
+        ..sol_chess::SearchLimits::default()
+    }
+}
+
+fn format_score(result: &sol_chess::SearchResult) -> String {
+    if result.is_mate() {
+        let plies = sol_chess::MATE_SCORE - result.score.abs();
+        let sign = if result.score > 0 { "" } else { "-" };
+        format!("{}M{}", sign, (plies + 1) / 2)
+    } else {
+        format!("{:+.2}", result.score as f64 / 100.0)
+    }
+}
+
+/// Best move for the side to move according to the built-in search.
+pub fn get_hint_line(game: &sol_chess::Game, think_time: u64) -> EngineResult<String> {
+    let color = game.get_current_player_color()?;
+    let result = sol_chess::search(game, get_search_limits(think_time))?;
+    Ok(match result.best_move {
+        Some((from, to)) => format!(
+            "hint: {} ({}, depth {})",
+            game.get_san(color, from, to),
+            format_score(&result),
+            result.depth
+        ),
+        None => "hint: no legal move".to_string(),
+    })
+}
+
+pub fn hint(client: &Client, game: Pubkey, think_time: u64) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    if !game_account.is_still_going() {
+        return Err("the game is not being played".into());
+    }
+    println!("{}", get_hint_line(&game_account, think_time)?);
+
+    Ok(())
+}
+
+/// Reviews a game from its move log and lists its blunders.
+pub fn analyze(client: &Client, game: Pubkey, think_time: u64, threshold: i32) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    let move_log = sol_chess_sdk::fetch_move_log(client, game)?;
+
+    let blunders = sol_chess::find_blunders(
+        &move_log,
+        game_account.game_config,
+        get_search_limits(think_time),
+        threshold,
+    )?;
+    if blunders.is_empty() {
+        println!("no blunders");
+    }
+    for blunder in blunders {
+        let dots = if blunder.color.is_white() { "." } else { "..." };
+        println!(
+            "{}{} {} loses {} centipawns, best was {}",
+            blunder.ply.div_ceil(2),
+            dots,
+            to_uci_move(blunder.played.0, blunder.played.1),
+            blunder.loss,
+            to_uci_move(blunder.best.0, blunder.best.1)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        sol_chess::{Piece, Square},
+    };
+
+    fn square(algebraic: &str) -> Square {
+        Square::from_algebraic(algebraic).unwrap()
+    }
+
+    // white to move with only `pieces` on the board
+    fn create_game(pieces: &[(Piece, &str)]) -> sol_chess::Game {
+        let mut game =
+            sol_chess::Game::create(sol_chess::GameConfig::default(), 0, Pubkey::default(), 0, 0);
+        game.start_game(0);
+        game.board = sol_chess::Board {
+            board: [[Piece::Empty; 8]; 8],
+        };
+        game.castling_right = sol_chess::CastlingRight {
+            white_kingside: false,
+            white_queenside: false,
+            black_kingside: false,
+            black_queenside: false,
+        };
+        for &(piece, algebraic) in pieces {
+            game.board.set_piece(piece, square(algebraic));
+        }
+        game
+    }
+
+    fn search_result(score: i32) -> sol_chess::SearchResult {
+        sol_chess::SearchResult {
+            best_move: None,
+            score,
+            depth: 1,
+            nodes: 1,
+        }
+    }
+
+    #[test]
+    fn random_mover_plays_the_only_legal_move() {
+        let game = create_game(&[
+            (Piece::WhiteKing, "a1"),
+            (Piece::BlackRook, "b8"),
+            (Piece::BlackKing, "c3"),
+        ]);
+
+        let best_move = RandomMover
+            .get_best_move(&game, sol_chess::Color::White)
+            .unwrap();
+
+        assert_eq!(best_move, (square("a1"), square("a2")));
+    }
+
+    #[test]
+    fn search_engine_finds_back_rank_mate() {
+        let game = create_game(&[
+            (Piece::BlackKing, "h8"),
+            (Piece::BlackPawn, "g7"),
+            (Piece::BlackPawn, "h7"),
+            (Piece::WhiteRook, "a1"),
+            (Piece::WhiteKing, "g1"),
+        ]);
+        let mut engine = SearchEngine {
+            limits: sol_chess::SearchLimits::new(3, Duration::from_secs(10)),
+        };
+
+        let best_move = engine
+            .get_best_move(&game, sol_chess::Color::White)
+            .unwrap();
+
+        assert_eq!(best_move, (square("a1"), square("a8")));
+    }
+
+    #[test]
+    fn engine_moves_queen_promotions() {
+        let game = create_game(&[
+            (Piece::WhiteKing, "a1"),
+            (Piece::WhitePawn, "e7"),
+            (Piece::WhiteKnight, "g1"),
+            (Piece::BlackKing, "a8"),
+        ]);
+
+        assert_eq!(to_engine_move(&game, square("e7"), square("e8")), "e7e8q");
+        assert_eq!(to_engine_move(&game, square("g1"), square("f3")), "g1f3");
+    }
+
+    #[test]
+    fn formats_scores_in_pawns_and_mates_in_moves() {
+        assert_eq!(format_score(&search_result(150)), "+1.50");
+        assert_eq!(format_score(&search_result(-25)), "-0.25");
+        assert_eq!(format_score(&search_result(0)), "+0.00");
+        assert_eq!(
+            format_score(&search_result(sol_chess::MATE_SCORE - 1)),
+            "M1"
+        );
+        assert_eq!(
+            format_score(&search_result(sol_chess::MATE_SCORE - 3)),
+            "M2"
+        );
+        assert_eq!(
+            format_score(&search_result(-(sol_chess::MATE_SCORE - 2))),
+            "-M1"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use {
+    crate::*,
+    clap::{ArgEnum, Args, Parser, Subcommand},
+};
+
+#[derive(Parser)]
+#[clap(
+    name = "sol-chess",
+    about = "Play chess against other wallets on Solana"
+)]
+pub struct Cli {
+    /// Solana cli config file, defaults to the solana cli's own
+    #[clap(long, short = 'C', global = true)]
+    pub config: Option<String>,
+    /// RPC url, overrides the config file
+    #[clap(long, short = 'u', global = true)]
+    pub url: Option<String>,
+    /// Keypair paying and signing, overrides the config file
+    #[clap(long, short = 'k', global = true)]
+    pub keypair: Option<String>,
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the user account of the keypair
+    InitUser,
+    /// Create a game and take a seat in it
+    CreateGame(CreateGameArgs),
+    /// Take the open seat of a game
+    JoinGame {
+        game: Pubkey,
+        #[clap(long)]
+        invite_code: Option<String>,
+    },
+    /// Leave a game that has not started yet
+    LeaveGame { game: Pubkey },
+    /// Close a game you created before anyone joins, refunding the wager
+    CancelGame { game: Pubkey },
+    /// Abort a game before both players have moved
+    Abort { game: Pubkey },
+    /// Play a move in SAN (Nf3) or UCI (g1f3)
+    Move {
+        game: Pubkey,
+        #[clap(name = "MOVE")]
+        mv: String,
+    },
+    /// Offer a draw, or accept the opponent's offer
+    OfferDraw { game: Pubkey },
+    /// Decline the opponent's draw offer
+    DeclineDraw { game: Pubkey },
+    /// Resign a game being played
+    Resign { game: Pubkey },
+    /// Ask the opponent to take back the last move
+    RequestTakeback { game: Pubkey },
+    /// Take back the moves the opponent asked for
+    AcceptTakeback { game: Pubkey },
+    /// Refuse the opponent's takeback request
+    DeclineTakeback { game: Pubkey },
+    /// Win, or end without a result, a game the opponent stopped moving in
+    ClaimAbandonment {
+        game: Pubkey,
+        /// Refund both players instead of claiming the win
+        #[clap(long)]
+        no_contest: bool,
+    },
+    /// Settle a game whose player to move has run out of time
+    CheckTimer { game: Pubkey },
+    /// Offer the opponent of a finished game a rematch
+    OfferRematch { game: Pubkey },
+    /// Accept the rematch offered after a finished game
+    AcceptRematch { game: Pubkey },
+    /// Wait in the matchmaking queue of a time control
+    Enqueue(QueueArgs),
+    /// Leave the matchmaking queue of a time control
+    Dequeue(QueueArgs),
+    /// Pair the first two compatible users of a matchmaking queue
+    MatchPlayers(QueueArgs),
+    /// Create the matchmaking queue of a time control
+    InitQueue(QueueArgs),
+    /// Create the leaderboard of a time category
+    InitLeaderboard {
+        #[clap(arg_enum)]
+        category: CategoryArg,
+    },
+    /// Create a lobby page
+    InitLobbyPage { page: u32 },
+    /// Limit the games the keypair can play at once
+    SetMaxActiveGames { max_active_games: u8 },
+    /// Deposit lamports to wager with
+    Deposit { amount: u64 },
+    /// Withdraw lamports not at stake
+    Withdraw { amount: u64 },
+    /// Show the position and clocks of a game
+    ShowGame { game: Pubkey },
+    /// Show a user, the keypair's by default
+    ShowUser { wallet: Option<Pubkey> },
+    /// Play a game interactively in the terminal
+    Play {
+        game: Pubkey,
+        /// Seconds between refreshes of the game account
+        #[clap(long, default_value_t = 2)]
+        refresh: u64,
+    },
+    /// Let an engine play a game, a random mover by default
+    Bot(BotArgs),
+    /// Suggest a move with the built-in engine
+    Hint {
+        game: Pubkey,
+        /// Milliseconds to think
+        #[clap(long, default_value_t = 2000)]
+        think_time: u64,
+    },
+    /// List the blunders of a game with the built-in engine
+    Analyze {
+        game: Pubkey,
+        /// Milliseconds to think per position
+        #[clap(long, default_value_t = 500)]
+        think_time: u64,
+        /// Centipawns lost for a move to count as a blunder
+        #[clap(long, default_value_t = sol_chess::DEFAULT_BLUNDER_THRESHOLD)]
+        threshold: i32,
+    },
+    /// Follow a game live over a websocket subscription
+    Watch {
+        game: Pubkey,
+        /// Print updates as JSON lines
+        #[clap(long)]
+        json: bool,
+    },
+    /// List the open games of the lobby
+    ListSeeks(ListSeeksArgs),
+}
+
+#[derive(Args)]
+pub struct CreateGameArgs {
+    /// Seconds on each clock
+    #[clap(long, default_value_t = 600)]
+    pub timer: u32,
+    /// Seconds added after each move
+    #[clap(long, default_value_t = 0)]
+    pub increment: u32,
+    #[clap(long)]
+    pub rated: bool,
+    /// Lamports each player puts at stake
+    #[clap(long)]
+    pub wager: Option<u64>,
+    /// Color of the creator, a random color can be chosen by a determined joiner
+    #[clap(long, arg_enum, default_value_t = ColorArg::Random)]
+    pub color: ColorArg,
+    /// Allow takebacks in a rated game
+    #[clap(long)]
+    pub allow_takebacks: bool,
+    /// Seconds without a move before abandonment can be claimed
+    #[clap(long)]
+    pub inactivity_threshold: Option<u32>,
+    #[clap(long, default_value_t = 0)]
+    pub lobby_page: u32,
+    #[clap(long, requires = "max-rating")]
+    pub min_rating: Option<u32>,
+    #[clap(long, requires = "min-rating")]
+    pub max_rating: Option<u32>,
+    /// Only let this wallet join
+    #[clap(long, conflicts_with = "invite-code")]
+    pub invite_wallet: Option<Pubkey>,
+    /// Only let players knowing this code join
+    #[clap(long)]
+    pub invite_code: Option<String>,
+}
+
+// a queue is keyed by every game setting but the color, which matchmaking
+// assigns
+#[derive(Args)]
+pub struct QueueArgs {
+    /// Seconds on each clock
+    #[clap(long, default_value_t = 600)]
+    pub timer: u32,
+    /// Seconds added after each move
+    #[clap(long, default_value_t = 0)]
+    pub increment: u32,
+    #[clap(long)]
+    pub rated: bool,
+    /// Lamports each player puts at stake
+    #[clap(long)]
+    pub wager: Option<u64>,
+    /// Allow takebacks in rated games
+    #[clap(long)]
+    pub allow_takebacks: bool,
+    /// Seconds without a move before abandonment can be claimed
+    #[clap(long)]
+    pub inactivity_threshold: Option<u32>,
+}
+
+impl From<QueueArgs> for sol_chess::GameConfig {
+    fn from(args: QueueArgs) -> Self {
+        sol_chess::GameConfig {
+            timer: args.timer,
+            increment: args.increment,
+            is_rated: args.rated,
+            wager: args.wager,
+            allow_takebacks: args.allow_takebacks,
+            inactivity_threshold: args.inactivity_threshold,
+            color: sol_chess::ColorPreference::Random,
+        }
+    }
+}
+
+// every unset filter matches
+#[derive(Args)]
+pub struct ListSeeksArgs {
+    #[clap(long, arg_enum)]
+    pub category: Option<CategoryArg>,
+    #[clap(long)]
+    pub rated: Option<bool>,
+    /// Most lamports you are willing to put at stake
+    #[clap(long)]
+    pub max_wager: Option<u64>,
+    /// Color you want to play, games with a random color always match
+    #[clap(long, arg_enum)]
+    pub color: Option<SideArg>,
+    /// Your rating, games whose rating range excludes it are hidden
+    #[clap(long)]
+    pub elo: Option<u32>,
+}
+
+impl From<ListSeeksArgs> for sol_chess_sdk::SeekFilter {
+    fn from(args: ListSeeksArgs) -> Self {
+        sol_chess_sdk::SeekFilter {
+            category: args.category.map(Into::into),
+            is_rated: args.rated,
+            max_wager: args.max_wager,
+            color: args.color.map(Into::into),
+            elo: args.elo,
+        }
+    }
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CategoryArg {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    Correspondence,
+}
+
+impl From<CategoryArg> for sol_chess::TimeCategory {
+    fn from(category: CategoryArg) -> Self {
+        match category {
+            CategoryArg::Bullet => sol_chess::TimeCategory::Bullet,
+            CategoryArg::Blitz => sol_chess::TimeCategory::Blitz,
+            CategoryArg::Rapid => sol_chess::TimeCategory::Rapid,
+            CategoryArg::Classical => sol_chess::TimeCategory::Classical,
+            CategoryArg::Correspondence => sol_chess::TimeCategory::Correspondence,
+        }
+    }
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorArg {
+    White,
+    Black,
+    Random,
+}
+
+impl From<ColorArg> for sol_chess::ColorPreference {
+    fn from(color: ColorArg) -> Self {
+        match color {
+            ColorArg::White => sol_chess::ColorPreference::White,
+            ColorArg::Black => sol_chess::ColorPreference::Black,
+            ColorArg::Random => sol_chess::ColorPreference::Random,
+        }
+    }
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SideArg {
+    White,
+    Black,
+}
+
+impl From<SideArg> for sol_chess::Color {
+    fn from(side: SideArg) -> Self {
+        match side {
+            SideArg::White => sol_chess::Color::White,
+            SideArg::Black => sol_chess::Color::Black,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct BotArgs {
+    pub game: Pubkey,
+    /// Path of a UCI engine binary
+    #[clap(long)]
+    pub engine: Option<String>,
+    /// Play with the built-in engine
+    #[clap(long, conflicts_with = "engine")]
+    pub builtin: bool,
+    /// Milliseconds the built-in engine thinks per move
+    #[clap(long, default_value_t = 1000)]
+    pub think_time: u64,
+    /// Seconds between refreshes of the game account
+    #[clap(long, default_value_t = 2)]
+    pub refresh: u64,
+}
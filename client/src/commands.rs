@@ -0,0 +1,195 @@
+use crate::*;
+
+pub type CommandResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+pub fn get_user_key(client: &Client) -> Pubkey {
+    sol_chess_sdk::pda::user(client.payer_pubkey())
+}
+
+pub fn get_player_color(
+    client: &Client,
+    game: Pubkey,
+    game_account: &sol_chess::Game,
+) -> std::result::Result<sol_chess::Color, sol_chess_sdk::SdkError> {
+    game_account
+        .get_player_color(get_user_key(client))
+        .map_err(|_| sol_chess_sdk::SdkError::NotInGame(game))
+}
+
+pub fn run(cli: Cli) -> CommandResult {
+    let config = CliConfig::load(cli.config.as_deref(), cli.url, cli.keypair)?;
+    let client = config.get_client()?;
+
+    match cli.command {
+        Command::InitUser => initialize_user(&client)?,
+        Command::CreateGame(args) => create_game(&client, args)?,
+        Command::JoinGame { game, invite_code } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            join_game(&client, game, &game_account, invite_code)?
+        }
+        Command::LeaveGame { game } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            leave_game(&client, game, &game_account)?
+        }
+        Command::CancelGame { game } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            cancel_game(&client, game, &game_account)?
+        }
+        Command::Abort { game } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            abort(&client, game, &game_account)?
+        }
+        Command::Move { game, mv } => play_move(&client, game, &mv)?,
+        Command::OfferDraw { game } => play_draw_offer(&client, game)?,
+        Command::DeclineDraw { game } => decline_draw(&client, game)?,
+        Command::Resign { game } => play_resignation(&client, game)?,
+        Command::RequestTakeback { game } => request_takeback(&client, game)?,
+        Command::AcceptTakeback { game } => accept_takeback(&client, game)?,
+        Command::DeclineTakeback { game } => decline_takeback(&client, game)?,
+        Command::ClaimAbandonment { game, no_contest } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            claim_abandonment(&client, game, &game_account, !no_contest)?
+        }
+        Command::CheckTimer { game } => {
+            let game_account = sol_chess_sdk::fetch_game(&client, game)?;
+            check_timer(&client, game, &game_account)?
+        }
+        Command::OfferRematch { game } => offer_rematch(&client, game)?,
+        Command::AcceptRematch { game } => play_rematch(&client, game)?,
+        Command::Enqueue(args) => enqueue(&client, &args.into())?,
+        Command::Dequeue(args) => dequeue(&client, &args.into())?,
+        Command::MatchPlayers(args) => match_queued_players(&client, &args.into())?,
+        Command::InitQueue(args) => initialize_matchmaking_queue(&client, args.into())?,
+        Command::InitLeaderboard { category } => initialize_leaderboard(&client, category.into())?,
+        Command::InitLobbyPage { page } => initialize_lobby_page(&client, page)?,
+        Command::SetMaxActiveGames { max_active_games } => {
+            set_max_active_games(&client, max_active_games)?
+        }
+        Command::Deposit { amount } => deposit(&client, amount)?,
+        Command::Withdraw { amount } => withdraw(&client, amount)?,
+        Command::ShowGame { game } => print_game(game, &sol_chess_sdk::fetch_game(&client, game)?),
+        Command::ShowUser { wallet } => {
+            let authority = wallet.unwrap_or_else(|| client.payer_pubkey());
+            let user = sol_chess_sdk::pda::user(authority);
+            print_user(user, &sol_chess_sdk::fetch_user(&client, authority)?)
+        }
+        Command::Play { game, refresh } => play(&client, game, refresh)?,
+        Command::Bot(args) => bot(&client, args)?,
+        Command::Hint { game, think_time } => hint(&client, game, think_time)?,
+        Command::Analyze {
+            game,
+            think_time,
+            threshold,
+        } => analyze(&client, game, think_time, threshold)?,
+        Command::Watch { game, json } => watch(&config, &client, game, json)?,
+        Command::ListSeeks(args) => {
+            print_seeks(&sol_chess_sdk::fetch_seeks(&client, &args.into())?)
+        }
+    }
+
+    Ok(())
+}
+
+fn create_game(client: &Client, args: CreateGameArgs) -> CommandResult {
+    let user = get_user_key(client);
+    let user_account = sol_chess_sdk::fetch_user(client, client.payer_pubkey())?;
+    let game = sol_chess_sdk::pda::game(user, user_account.games_created);
+
+    let rating_range = match (args.min_rating, args.max_rating) {
+        (Some(min), Some(max)) => Some(sol_chess::RatingRange { min, max }),
+        _ => None,
+    };
+    let invite = match (args.invite_wallet, args.invite_code) {
+        (Some(wallet), _) => Some(sol_chess::Invite::User(sol_chess_sdk::pda::user(wallet))),
+        (None, Some(code)) => Some(sol_chess::Invite::Code(sol_chess::Invite::hash_code(&code))),
+        (None, None) => None,
+    };
+
+    initialize_game(
+        client,
+        user_account.games_created,
+        args.lobby_page,
+        sol_chess::GameConfig {
+            wager: args.wager,
+            timer: args.timer,
+            increment: args.increment,
+            is_rated: args.rated,
+            color: args.color.into(),
+            allow_takebacks: args.allow_takebacks,
+            inactivity_threshold: args.inactivity_threshold,
+        },
+        rating_range,
+        invite,
+    )?;
+    println!("game: {}", game);
+
+    Ok(())
+}
+
+pub fn play_move(client: &Client, game: Pubkey, mv: &str) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    if !game_account.is_still_going() {
+        return Err("the game is not being played".into());
+    }
+
+    let color = get_player_color(client, game, &game_account)?;
+    if game_account.get_current_player_color()? != color {
+        return Err("it is not your turn".into());
+    }
+
+    let (from, to) = parse_move(&game_account, color, mv)
+        .ok_or_else(|| format!("{} is not a legal move", mv))?;
+    move_piece(client, game, &game_account, from, to)?;
+
+    Ok(())
+}
+
+pub fn play_draw_offer(client: &Client, game: Pubkey) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    offer_draw(client, game, &game_account)?;
+
+    Ok(())
+}
+
+pub fn play_resignation(client: &Client, game: Pubkey) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    resign(client, game, &game_account)?;
+
+    Ok(())
+}
+
+// the rematch is the next game of the accepting user
+fn play_rematch(client: &Client, game: Pubkey) -> CommandResult {
+    let game_account = sol_chess_sdk::fetch_game(client, game)?;
+    let user_account = sol_chess_sdk::fetch_user(client, client.payer_pubkey())?;
+    let rematch_game = sol_chess_sdk::pda::game(get_user_key(client), user_account.games_created);
+    accept_rematch(client, game, &game_account, user_account.games_created)?;
+    println!("game: {}", rematch_game);
+
+    Ok(())
+}
+
+fn match_queued_players(client: &Client, game_config: &sol_chess::GameConfig) -> CommandResult {
+    let queue = sol_chess_sdk::fetch_matchmaking_queue(client, game_config)?;
+    let current_timestamp = get_current_timestamp();
+    let entries = queue.get_entries();
+    let (entry, adversary_entry) = entries
+        .iter()
+        .enumerate()
+        .flat_map(|(index, entry)| entries[index + 1..].iter().map(move |other| (entry, other)))
+        .find(|(entry, other)| entry.is_compatible(other, current_timestamp))
+        .ok_or("no two queued users are within rating range")?;
+
+    let user_account: sol_chess::User = sol_chess_sdk::fetch_account(client, entry.user)?;
+    let game = sol_chess_sdk::pda::game(entry.user, user_account.games_created);
+    match_players(
+        client,
+        entry.user,
+        adversary_entry.user,
+        game_config,
+        user_account.games_created,
+    )?;
+    println!("game: {}", game);
+
+    Ok(())
+}
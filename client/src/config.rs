@@ -0,0 +1,80 @@
+use {
+    crate::*,
+    solana_cli_config::{Config, CONFIG_FILE},
+    solana_sdk::signature::read_keypair_file,
+};
+
+/// Connection settings, flags take precedence over the solana cli config file.
+/// A config file given explicitly must load, the solana cli's own one falls
+/// back to the defaults when it is missing.
+pub struct CliConfig {
+    pub json_rpc_url: String,
+    pub websocket_url: String,
+    pub keypair_path: String,
+}
+
+impl CliConfig {
+    pub fn load(
+        config_file: Option<&str>,
+        json_rpc_url: Option<String>,
+        keypair_path: Option<String>,
+    ) -> std::result::Result<Self, String> {
+        let config = match config_file {
+            Some(config_file) => Config::load(config_file)
+                .map_err(|err| format!("failed to load config {}: {}", config_file, err))?,
+            None => CONFIG_FILE
+                .as_deref()
+                .and_then(|config_file| Config::load(config_file).ok())
+                .unwrap_or_default(),
+        };
+
+        let json_rpc_url = json_rpc_url.unwrap_or(config.json_rpc_url);
+        let websocket_url = Config::compute_websocket_url(&json_rpc_url);
+        Ok(Self {
+            json_rpc_url,
+            websocket_url,
+            keypair_path: keypair_path.unwrap_or(config.keypair_path),
+        })
+    }
+
+    pub fn get_client(&self) -> std::result::Result<Client, String> {
+        let payer = read_keypair_file(&self.keypair_path)
+            .map_err(|err| format!("failed to read keypair {}: {}", self.keypair_path, err))?;
+        Ok(Client::new(payer, self.json_rpc_url.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::fs};
+
+    #[test]
+    fn an_explicit_config_file_must_load() {
+        let directory =
+            std::env::temp_dir().join(format!("sol-chess-config-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let missing = directory.join("missing.yml");
+        let malformed = directory.join("malformed.yml");
+        fs::write(&malformed, "json_rpc_url: [").unwrap();
+
+        for config_file in [&missing, &malformed] {
+            assert!(CliConfig::load(config_file.to_str(), None, None).is_err());
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn flags_override_the_config_file() {
+        let config = CliConfig::load(
+            None,
+            Some("http://localhost:8899".to_string()),
+            Some("id.json".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(config.json_rpc_url, "http://localhost:8899");
+        assert_eq!(config.websocket_url, "ws://localhost:8900/");
+        assert_eq!(config.keypair_path, "id.json");
+    }
+}
@@ -0,0 +1,9 @@
+use crate::*;
+
+pub fn abort(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let abort_ix = sol_chess_sdk::abort(client.payer_pubkey(), game, game_account)?;
+
+    send_and_confirm_tx(client, [abort_ix].to_vec(), None, "abort".to_string())?;
+
+    Ok(())
+}
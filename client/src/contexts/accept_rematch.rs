@@ -0,0 +1,20 @@
+use crate::*;
+
+pub fn accept_rematch(
+    client: &Client,
+    game: Pubkey,
+    game_account: &sol_chess::Game,
+    game_id: u64,
+) -> SdkResult<()> {
+    let accept_rematch_ix =
+        sol_chess_sdk::accept_rematch(client.payer_pubkey(), game, game_account, game_id)?;
+
+    send_and_confirm_tx(
+        client,
+        [accept_rematch_ix].to_vec(),
+        None,
+        "accept_rematch".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn accept_takeback(client: &Client, game: Pubkey) -> SdkResult<()> {
+    let accept_takeback_ix = sol_chess_sdk::accept_takeback(client.payer_pubkey(), game);
+
+    send_and_confirm_tx(
+        client,
+        [accept_takeback_ix].to_vec(),
+        None,
+        "accept_takeback".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn cancel_game(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let cancel_game_ix = sol_chess_sdk::cancel_game(client.payer_pubkey(), game, game_account);
+
+    send_and_confirm_tx(
+        client,
+        [cancel_game_ix].to_vec(),
+        None,
+        "cancel_game".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn check_timer(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let check_timer_ix = sol_chess_sdk::check_timer(client.payer_pubkey(), game, game_account)?;
+
+    send_and_confirm_tx(
+        client,
+        [check_timer_ix].to_vec(),
+        None,
+        "check_timer".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::*;
+
+pub fn claim_abandonment(
+    client: &Client,
+    game: Pubkey,
+    game_account: &sol_chess::Game,
+    claim_win: bool,
+) -> SdkResult<()> {
+    let claim_abandonment_ix =
+        sol_chess_sdk::claim_abandonment(client.payer_pubkey(), game, game_account, claim_win)?;
+
+    send_and_confirm_tx(
+        client,
+        [claim_abandonment_ix].to_vec(),
+        None,
+        "claim_abandonment".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn decline_draw(client: &Client, game: Pubkey) -> SdkResult<()> {
+    let decline_draw_ix = sol_chess_sdk::decline_draw(client.payer_pubkey(), game);
+
+    send_and_confirm_tx(
+        client,
+        [decline_draw_ix].to_vec(),
+        None,
+        "decline_draw".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn decline_takeback(client: &Client, game: Pubkey) -> SdkResult<()> {
+    let decline_takeback_ix = sol_chess_sdk::decline_takeback(client.payer_pubkey(), game);
+
+    send_and_confirm_tx(
+        client,
+        [decline_takeback_ix].to_vec(),
+        None,
+        "decline_takeback".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -1,21 +1,12 @@
 use crate::*;
 
-pub fn deposit(client: &Client, user: Pubkey, amount: u64) -> ClientResult<()> {
-    let vault = Pubkey::find_program_address(&[b"vault"], &sol_chess::ID).0;
-    client.airdrop(&vault, 1000000)?;
+pub fn deposit(client: &Client, amount: u64) -> SdkResult<()> {
+    let airdrop = client.request_airdrop(&sol_chess_sdk::pda::vault(), 1000000)?;
+    client.poll_for_signature(&airdrop)?;
 
-    let deposit_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new(vault, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-        ],
-        data: sol_chess::instruction::Deposit { amount }.data(),
-    };
+    let deposit_ix = sol_chess_sdk::deposit(client.payer_pubkey(), amount);
 
-    send_and_confirm_tx(&client, [deposit_ix].to_vec(), None, "deposit".to_string())?;
+    send_and_confirm_tx(client, [deposit_ix].to_vec(), None, "deposit".to_string())?;
 
     Ok(())
 }
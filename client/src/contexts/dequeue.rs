@@ -0,0 +1,9 @@
+use crate::*;
+
+pub fn dequeue(client: &Client, game_config: &sol_chess::GameConfig) -> SdkResult<()> {
+    let dequeue_ix = sol_chess_sdk::dequeue(client.payer_pubkey(), game_config);
+
+    send_and_confirm_tx(client, [dequeue_ix].to_vec(), None, "dequeue".to_string())?;
+
+    Ok(())
+}
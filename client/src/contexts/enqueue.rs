@@ -0,0 +1,9 @@
+use crate::*;
+
+pub fn enqueue(client: &Client, game_config: &sol_chess::GameConfig) -> SdkResult<()> {
+    let enqueue_ix = sol_chess_sdk::enqueue(client.payer_pubkey(), game_config);
+
+    send_and_confirm_tx(client, [enqueue_ix].to_vec(), None, "enqueue".to_string())?;
+
+    Ok(())
+}
@@ -2,36 +2,24 @@ use crate::*;
 
 pub fn initialize_game(
     client: &Client,
-    user: Pubkey,
-    game: Pubkey,
-    wager: Option<u64>,
-    timer: u32,
-    increment: u32,
-    is_rated: bool,
-) -> ClientResult<()> {
-    let initiallize_game_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new(game, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-            AccountMeta::new_readonly(clock::ID, false),
-        ],
-        data: sol_chess::instruction::InitializeGame {
-            game_config: sol_chess::GameConfig {
-                wager,
-                timer,
-                increment,
-                is_rated,
-            },
-        }
-        .data(),
-    };
+    game_id: u64,
+    lobby_page: u32,
+    game_config: sol_chess::GameConfig,
+    rating_range: Option<sol_chess::RatingRange>,
+    invite: Option<sol_chess::Invite>,
+) -> SdkResult<()> {
+    let initialize_game_ix = sol_chess_sdk::initialize_game(
+        client.payer_pubkey(),
+        game_id,
+        lobby_page,
+        game_config,
+        rating_range,
+        invite,
+    );
 
     send_and_confirm_tx(
-        &client,
-        [initiallize_game_ix].to_vec(),
+        client,
+        [initialize_game_ix].to_vec(),
         None,
         "initialize_game".to_string(),
     )?;
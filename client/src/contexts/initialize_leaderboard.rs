@@ -0,0 +1,15 @@
+use crate::*;
+
+pub fn initialize_leaderboard(client: &Client, category: sol_chess::TimeCategory) -> SdkResult<()> {
+    let initialize_leaderboard_ix =
+        sol_chess_sdk::initialize_leaderboard(client.payer_pubkey(), category);
+
+    send_and_confirm_tx(
+        client,
+        [initialize_leaderboard_ix].to_vec(),
+        None,
+        "initialize_leaderboard".to_string(),
+    )?;
+
+    Ok(())
+}
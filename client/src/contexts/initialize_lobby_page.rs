@@ -0,0 +1,15 @@
+use crate::*;
+
+pub fn initialize_lobby_page(client: &Client, page: u32) -> SdkResult<()> {
+    let initialize_lobby_page_ix =
+        sol_chess_sdk::initialize_lobby_page(client.payer_pubkey(), page);
+
+    send_and_confirm_tx(
+        client,
+        [initialize_lobby_page_ix].to_vec(),
+        None,
+        "initialize_lobby_page".to_string(),
+    )?;
+
+    Ok(())
+}
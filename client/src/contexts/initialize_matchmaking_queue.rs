@@ -0,0 +1,18 @@
+use crate::*;
+
+pub fn initialize_matchmaking_queue(
+    client: &Client,
+    game_config: sol_chess::GameConfig,
+) -> SdkResult<()> {
+    let initialize_matchmaking_queue_ix =
+        sol_chess_sdk::initialize_matchmaking_queue(client.payer_pubkey(), game_config);
+
+    send_and_confirm_tx(
+        client,
+        [initialize_matchmaking_queue_ix].to_vec(),
+        None,
+        "initialize_matchmaking_queue".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -1,19 +1,11 @@
 use crate::*;
 
-pub fn initialize_user(client: &Client, user: Pubkey) -> ClientResult<()> {
-    let initiallize_user_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-        ],
-        data: sol_chess::instruction::InitializeUser {}.data(),
-    };
+pub fn initialize_user(client: &Client) -> SdkResult<()> {
+    let initialize_user_ix = sol_chess_sdk::initialize_user(client.payer_pubkey());
 
     send_and_confirm_tx(
-        &client,
-        [initiallize_user_ix].to_vec(),
+        client,
+        [initialize_user_ix].to_vec(),
         None,
         "initialize_user".to_string(),
     )?;
@@ -2,22 +2,15 @@ use crate::*;
 
 pub fn join_game(
     client: &Client,
-    user: Pubkey,
     game: Pubkey,
-    color: sol_chess::Color,
-) -> ClientResult<()> {
-    let join_game_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new(game, false),
-        ],
-        data: sol_chess::instruction::JoinGame { color }.data(),
-    };
+    game_account: &sol_chess::Game,
+    invite_code: Option<String>,
+) -> SdkResult<()> {
+    let join_game_ix =
+        sol_chess_sdk::join_game(client.payer_pubkey(), game, game_account, invite_code);
 
     send_and_confirm_tx(
-        &client,
+        client,
         [join_game_ix].to_vec(),
         None,
         "join_game".to_string(),
@@ -1,23 +1,10 @@
 use crate::*;
 
-pub fn leave_game(
-    client: &Client,
-    user: Pubkey,
-    game: Pubkey,
-    color: sol_chess::Color,
-) -> ClientResult<()> {
-    let leave_game_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new(game, false),
-        ],
-        data: sol_chess::instruction::LeaveGame {}.data(),
-    };
+pub fn leave_game(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let leave_game_ix = sol_chess_sdk::leave_game(client.payer_pubkey(), game, game_account);
 
     send_and_confirm_tx(
-        &client,
+        client,
         [leave_game_ix].to_vec(),
         None,
         "leave_game".to_string(),
@@ -0,0 +1,26 @@
+use crate::*;
+
+pub fn match_players(
+    client: &Client,
+    user: Pubkey,
+    adversary_user: Pubkey,
+    game_config: &sol_chess::GameConfig,
+    game_id: u64,
+) -> SdkResult<()> {
+    let match_players_ix = sol_chess_sdk::match_players(
+        client.payer_pubkey(),
+        user,
+        adversary_user,
+        game_config,
+        game_id,
+    );
+
+    send_and_confirm_tx(
+        client,
+        [match_players_ix].to_vec(),
+        None,
+        "match_players".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn offer_draw(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let offer_draw_ix = sol_chess_sdk::offer_draw(client.payer_pubkey(), game, game_account)?;
+
+    send_and_confirm_tx(
+        client,
+        [offer_draw_ix].to_vec(),
+        None,
+        "offer_draw".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn offer_rematch(client: &Client, game: Pubkey) -> SdkResult<()> {
+    let offer_rematch_ix = sol_chess_sdk::offer_rematch(client.payer_pubkey(), game);
+
+    send_and_confirm_tx(
+        client,
+        [offer_rematch_ix].to_vec(),
+        None,
+        "offer_rematch".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+pub fn request_takeback(client: &Client, game: Pubkey) -> SdkResult<()> {
+    let request_takeback_ix = sol_chess_sdk::request_takeback(client.payer_pubkey(), game);
+
+    send_and_confirm_tx(
+        client,
+        [request_takeback_ix].to_vec(),
+        None,
+        "request_takeback".to_string(),
+    )?;
+
+    Ok(())
+}
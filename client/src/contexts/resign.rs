@@ -1,23 +1,9 @@
 use crate::*;
 
-pub fn resign(
-    client: &Client,
-    user: Pubkey,
-    adversary_user: Pubkey,
-    game: Pubkey,
-) -> ClientResult<()> {
-    let resign_ix = Instruction {
-        program_id: sol_chess::ID,
-        accounts: vec![
-            AccountMeta::new(client.payer_pubkey(), true),
-            AccountMeta::new(user, false),
-            AccountMeta::new(adversary_user, false),
-            AccountMeta::new(game, false),
-        ],
-        data: sol_chess::instruction::Resign {}.data(),
-    };
+pub fn resign(client: &Client, game: Pubkey, game_account: &sol_chess::Game) -> SdkResult<()> {
+    let resign_ix = sol_chess_sdk::resign(client.payer_pubkey(), game, game_account)?;
 
-    send_and_confirm_tx(&client, [resign_ix].to_vec(), None, "resign".to_string())?;
+    send_and_confirm_tx(client, [resign_ix].to_vec(), None, "resign".to_string())?;
 
     Ok(())
 }
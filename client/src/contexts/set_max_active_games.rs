@@ -0,0 +1,15 @@
+use crate::*;
+
+pub fn set_max_active_games(client: &Client, max_active_games: u8) -> SdkResult<()> {
+    let set_max_active_games_ix =
+        sol_chess_sdk::set_max_active_games(client.payer_pubkey(), max_active_games);
+
+    send_and_confirm_tx(
+        client,
+        [set_max_active_games_ix].to_vec(),
+        None,
+        "set_max_active_games".to_string(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::*;
+
+pub fn piece_char(piece: sol_chess::Piece) -> char {
+    match piece.get_san_letter() {
+        _ if piece.is_empty() => '.',
+        Some(letter) if piece.is_white() => letter,
+        Some(letter) => letter.to_ascii_lowercase(),
+        None if piece.is_white() => 'P',
+        None => 'p',
+    }
+}
+
+pub fn print_board(board: &sol_chess::Board) {
+    for rank in 0..8u8 {
+        let row: Vec<String> = (0..8u8)
+            .map(|file| piece_char(board.get_piece(sol_chess::Square { rank, file })).to_string())
+            .collect();
+        println!("{} {}", 8 - rank, row.join(" "));
+    }
+    println!("  a b c d e f g h");
+}
+
+const LIGHT_SQUARE: &str = "\x1b[48;5;180m";
+const DARK_SQUARE: &str = "\x1b[48;5;137m";
+const LAST_MOVE_SQUARE: &str = "\x1b[48;5;143m";
+const TARGET_SQUARE: &str = "\x1b[48;5;108m";
+const PIECE_COLOR: &str = "\x1b[38;5;16m";
+const RESET: &str = "\x1b[0m";
+
+fn piece_symbol(piece: sol_chess::Piece) -> char {
+    match piece {
+        sol_chess::Piece::Empty => ' ',
+        sol_chess::Piece::WhitePawn => '♙',
+        sol_chess::Piece::WhiteRook => '♖',
+        sol_chess::Piece::WhiteKnight => '♘',
+        sol_chess::Piece::WhiteBishop => '♗',
+        sol_chess::Piece::WhiteQueen => '♕',
+        sol_chess::Piece::WhiteKing => '♔',
+        sol_chess::Piece::BlackPawn => '♟',
+        sol_chess::Piece::BlackRook => '♜',
+        sol_chess::Piece::BlackKnight => '♞',
+        sol_chess::Piece::BlackBishop => '♝',
+        sol_chess::Piece::BlackQueen => '♛',
+        sol_chess::Piece::BlackKing => '♚',
+    }
+}
+
+/// Renders the board with Unicode pieces and ANSI colors, seen from the side
+/// of `perspective`, highlighting the last move and the given targets.
+pub fn render_board(
+    board: &sol_chess::Board,
+    perspective: sol_chess::Color,
+    last_move: Option<(sol_chess::Square, sol_chess::Square)>,
+    targets: &[sol_chess::Square],
+) -> String {
+    // rank 0 is the 8th rank, so white reads the board as stored and black
+    // reads both ranks and files backwards
+    let order: Vec<u8> = if perspective.is_white() {
+        (0..8).collect()
+    } else {
+        (0..8).rev().collect()
+    };
+
+    let mut output = String::new();
+    for &rank in &order {
+        output.push_str(&format!("{} ", 8 - rank));
+        for &file in &order {
+            let square = sol_chess::Square { rank, file };
+            let background = if targets.contains(&square) {
+                TARGET_SQUARE
+            } else if last_move.is_some_and(|(from, to)| square == from || square == to) {
+                LAST_MOVE_SQUARE
+            } else if (rank + file) % 2 == 0 {
+                LIGHT_SQUARE
+            } else {
+                DARK_SQUARE
+            };
+            output.push_str(&format!(
+                "{}{} {} {}",
+                background,
+                PIECE_COLOR,
+                piece_symbol(board.get_piece(square)),
+                RESET
+            ));
+        }
+        output.push('\n');
+    }
+    output.push(' ');
+    for &file in &order {
+        output.push_str(&format!("  {}", (b'a' + file) as char));
+    }
+    output.push('\n');
+    output
+}
+
+pub fn format_clock(seconds: u32) -> String {
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+pub fn print_game(key: Pubkey, game: &sol_chess::Game) {
+    let config = game.game_config;
+    println!("game:      {}", key);
+    println!("state:     {:?} (ply {})", game.game_state, game.ply);
+    println!("white:     {:?}", game.white);
+    println!("black:     {:?}", game.black);
+    println!(
+        "config:    {}+{} {:?} rated={} wager={}",
+        config.get_timer(),
+        config.get_increment(),
+        config.get_time_category(),
+        config.is_rated(),
+        config.get_wager_or_zero()
+    );
+    println!(
+        "clocks:    white {}s black {}s",
+        game.time_control.white_timer, game.time_control.black_timer
+    );
+    if let Some(offer) = game.draw_state.get_offer() {
+        println!(
+            "draw:      offered by {:?} on ply {}",
+            offer.color, offer.ply
+        );
+    }
+    println!();
+    print_board(&game.board);
+}
+
+pub fn print_user(key: Pubkey, user: &sol_chess::User) {
+    println!("user:      {}", key);
+    println!("balance:   {}", user.balance);
+    println!(
+        "games:     {}/{} active",
+        user.get_active_games_count(),
+        user.max_active_games
+    );
+    for game in user.get_active_games() {
+        println!("           {}", game);
+    }
+    for category in [
+        sol_chess::TimeCategory::Bullet,
+        sol_chess::TimeCategory::Blitz,
+        sol_chess::TimeCategory::Rapid,
+        sol_chess::TimeCategory::Classical,
+        sol_chess::TimeCategory::Correspondence,
+    ] {
+        println!(
+            "{:<10} {} ({} games)",
+            format!("{:?}:", category),
+            user.get_elo(category),
+            user.get_rated_games(category)
+        );
+    }
+    let stats = user.stats;
+    println!(
+        "stats:     {} played, {} won, {} lost, {} drawn, net {}",
+        stats.games_played, stats.wins, stats.losses, stats.draws, stats.net_winnings
+    );
+}
+
+pub fn print_seeks(seeks: &[sol_chess::Seek]) {
+    for seek in seeks {
+        println!(
+            "{} creator={} {:?} {}+{} rated={} wager={} color={:?} rating_range={:?}",
+            seek.game,
+            seek.creator,
+            seek.game_config.get_time_category(),
+            seek.game_config.get_timer(),
+            seek.game_config.get_increment(),
+            seek.game_config.is_rated(),
+            seek.game_config.get_wager_or_zero(),
+            seek.color,
+            seek.rating_range.map(|range| (range.min, range.max)),
+        );
+    }
+}
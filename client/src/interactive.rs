@@ -0,0 +1,207 @@
+use {
+    crate::*,
+    std::{
+        io::BufRead,
+        sync::mpsc::{self, Receiver, RecvTimeoutError},
+        thread,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const HELP: &str = "moves: e4, Nf3, e2e4 | e2: show targets | hint | draw | resign | flip | quit";
+// milliseconds the built-in engine thinks for a hint
+const HINT_THINK_TIME: u64 = 2000;
+
+enum Input {
+    Move(String),
+    Select(sol_chess::Square),
+    Hint,
+    Draw,
+    Resign,
+    Flip,
+    Quit,
+}
+
+impl Input {
+    // a square is a pawn push in SAN, so it only selects when it is not a
+    // legal move for the player
+    fn parse(line: &str, game: &sol_chess::Game, color: sol_chess::Color) -> Self {
+        match line.trim() {
+            "hint" => Input::Hint,
+            "draw" => Input::Draw,
+            "resign" => Input::Resign,
+            "flip" => Input::Flip,
+            "quit" | "q" => Input::Quit,
+            line => {
+                let is_turn = game.get_current_player_color().ok() == Some(color);
+                if is_turn && parse_move(game, color, line).is_some() {
+                    return Input::Move(line.to_string());
+                }
+                match sol_chess::Square::from_algebraic(line) {
+                    Some(square) => Input::Select(square),
+                    None => Input::Move(line.to_string()),
+                }
+            }
+        }
+    }
+}
+
+// stdin blocks, so lines are read on their own thread and polled alongside
+// the game account
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(|line| line.ok()) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+pub fn get_current_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+/// Clocks as they stand now, the side to move losing the time elapsed since
+/// the last move.
+pub fn get_clocks(game: &sol_chess::Game, current_timestamp: i64) -> (u32, u32) {
+    let time_control = game.time_control;
+    if !game.is_still_going() || time_control.is_first_move() {
+        return (time_control.white_timer, time_control.black_timer);
+    }
+
+    let time_passed = time_control.get_time_passed(current_timestamp).max(0) as u32;
+    if game.game_state.is_white_turn() {
+        (
+            time_control.white_timer.saturating_sub(time_passed),
+            time_control.black_timer,
+        )
+    } else {
+        (
+            time_control.white_timer,
+            time_control.black_timer.saturating_sub(time_passed),
+        )
+    }
+}
+
+fn render(
+    game: &sol_chess::Game,
+    color: sol_chess::Color,
+    perspective: sol_chess::Color,
+    selected: Option<sol_chess::Square>,
+    message: &str,
+) {
+    let (white_clock, black_clock) = get_clocks(game, get_current_timestamp());
+    let clock_line = |clock_color: sol_chess::Color| {
+        let (name, clock) = if clock_color.is_white() {
+            ("white", white_clock)
+        } else {
+            ("black", black_clock)
+        };
+        let you = if clock_color == color { " (you)" } else { "" };
+        let turn = if game.get_current_player_color().ok() == Some(clock_color) {
+            " *"
+        } else {
+            ""
+        };
+        format!("{}{} {}{}", name, you, format_clock(clock), turn)
+    };
+
+    let targets: Vec<sol_chess::Square> = match selected {
+        Some(from) if game.is_still_going() => game
+            .get_legal_moves(color)
+            .into_iter()
+            .filter(|&(legal_from, _)| legal_from == from)
+            .map(|(_, to)| to)
+            .collect(),
+        _ => vec![],
+    };
+    let last_move = game
+        .get_last_move()
+        .map(|last_move| (last_move.from, last_move.to));
+
+    print!("{}", CLEAR_SCREEN);
+    println!("{}", clock_line(perspective.get_opposite()));
+    print!(
+        "{}",
+        render_board(&game.board, perspective, last_move, &targets)
+    );
+    println!("{}", clock_line(perspective));
+    println!();
+    if game.game_state.is_waiting() {
+        println!("waiting for an opponent");
+    } else if game.is_finished() {
+        println!("game over: {:?}", game.game_state);
+    } else if let Some(offer) = game.draw_state.get_offer() {
+        println!("{:?} offers a draw", offer.color);
+    }
+    if !message.is_empty() {
+        println!("{}", message);
+    }
+    println!("{}", HELP);
+}
+
+/// Interactive game in the terminal: renders the board from the player's
+/// side, takes typed moves and refreshes whenever the game account changes.
+pub fn play(client: &Client, game: Pubkey, refresh: u64) -> CommandResult {
+    let mut game_account = sol_chess_sdk::fetch_game(client, game)?;
+    let color = get_player_color(client, game, &game_account)?;
+    let mut perspective = color;
+    let mut selected = None;
+    let mut message = String::new();
+    let lines = spawn_stdin_reader();
+    let mut redraw = true;
+
+    loop {
+        if redraw {
+            render(&game_account, color, perspective, selected, &message);
+            if game_account.is_finished() {
+                return Ok(());
+            }
+        }
+
+        redraw = true;
+        let input = match lines.recv_timeout(Duration::from_secs(refresh)) {
+            Ok(line) => Input::parse(&line, &game_account, color),
+            Err(RecvTimeoutError::Timeout) => {
+                // only redraw on changes so a move being typed is not cleared
+                let latest = sol_chess_sdk::fetch_game(client, game)?;
+                redraw = latest.try_to_vec()? != game_account.try_to_vec()?;
+                game_account = latest;
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+
+        message.clear();
+        selected = None;
+        let result = match input {
+            Input::Select(square) => {
+                selected = Some(square);
+                Ok(())
+            }
+            Input::Hint => {
+                message = get_hint_line(&game_account, HINT_THINK_TIME)
+                    .unwrap_or_else(|err| err.to_string());
+                Ok(())
+            }
+            Input::Move(mv) => play_move(client, game, &mv),
+            Input::Draw => play_draw_offer(client, game),
+            Input::Resign => play_resignation(client, game),
+            Input::Flip => {
+                perspective = perspective.get_opposite();
+                Ok(())
+            }
+            Input::Quit => return Ok(()),
+        };
+        if let Err(err) = result {
+            message = err.to_string();
+        }
+        game_account = sol_chess_sdk::fetch_game(client, game)?;
+    }
+}
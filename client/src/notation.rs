@@ -0,0 +1,272 @@
+use crate::*;
+
+/// Parses a move typed in UCI (`e2e4`, `e7e8q`) or SAN (`e4`, `Nxf3+`) for
+/// the side to move.
+pub fn parse_move(
+    game: &sol_chess::Game,
+    color: sol_chess::Color,
+    input: &str,
+) -> Option<(sol_chess::Square, sol_chess::Square)> {
+    let input = input.trim();
+    if let Some(uci_move) = parse_uci_move(input) {
+        return Some(uci_move);
+    }
+
+    let san = input.trim_end_matches(['+', '#', '!', '?']);
+    let san = san.replace('0', "O");
+    game.get_legal_moves(color)
+        .into_iter()
+        .find(|&(from, to)| game.get_san(color, from, to) == san)
+}
+
+// promotions always queen on chain, so the promotion letter is ignored
+pub fn parse_uci_move(input: &str) -> Option<(sol_chess::Square, sol_chess::Square)> {
+    if input.len() != 4 && input.len() != 5 {
+        return None;
+    }
+    let from = sol_chess::Square::from_algebraic(input.get(0..2)?)?;
+    let to = sol_chess::Square::from_algebraic(input.get(2..4)?)?;
+    Some((from, to))
+}
+
+pub fn to_uci_move(from: sol_chess::Square, to: sol_chess::Square) -> String {
+    format!("{}{}", from.to_algebraic(), to.to_algebraic())
+}
+
+/// FEN of the position, the halfmove clock is not tracked on chain and is
+/// always 0.
+pub fn to_fen(game: &sol_chess::Game) -> String {
+    let mut placement = vec![];
+    // rank 0 is the 8th rank, the first one FEN lists
+    for rank in 0..8u8 {
+        let mut row = String::new();
+        let mut empty = 0;
+        for file in 0..8u8 {
+            let piece = game.board.get_piece(sol_chess::Square { rank, file });
+            if piece.is_empty() {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 {
+                row.push_str(&empty.to_string());
+                empty = 0;
+            }
+            row.push(piece_char(piece));
+        }
+        if empty > 0 {
+            row.push_str(&empty.to_string());
+        }
+        placement.push(row);
+    }
+
+    let side_to_move = if game.game_state.is_white_turn() {
+        "w"
+    } else {
+        "b"
+    };
+
+    let castling_right = game.castling_right;
+    let mut castling: String = [
+        (castling_right.white_kingside, 'K'),
+        (castling_right.white_queenside, 'Q'),
+        (castling_right.black_kingside, 'k'),
+        (castling_right.black_queenside, 'q'),
+    ]
+    .iter()
+    .filter(|(has_right, _)| *has_right)
+    .map(|(_, letter)| *letter)
+    .collect();
+    if castling.is_empty() {
+        castling.push('-');
+    }
+
+    let enpassant = game
+        .enpassant
+        .map_or("-".to_string(), |square| square.to_algebraic());
+
+    format!(
+        "{} {} {} {} 0 {}",
+        placement.join("/"),
+        side_to_move,
+        castling,
+        enpassant,
+        game.ply / 2 + 1
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        sol_chess::{Color, Piece, Square},
+    };
+
+    fn square(algebraic: &str) -> Square {
+        Square::from_algebraic(algebraic).unwrap()
+    }
+
+    fn start_game() -> sol_chess::Game {
+        let game_config = sol_chess::GameConfig {
+            timer: 600,
+            ..sol_chess::GameConfig::default()
+        };
+        let mut game = sol_chess::Game::create(game_config, 0, Pubkey::default(), 0, 0);
+        game.join_game(Pubkey::new_unique(), Color::White);
+        game.join_game(Pubkey::new_unique(), Color::Black);
+        game.start_game(0);
+        game
+    }
+
+    // white to move with only `pieces` on the board
+    fn create_game(pieces: &[(Piece, &str)]) -> sol_chess::Game {
+        let mut game = start_game();
+        game.board = sol_chess::Board {
+            board: [[Piece::Empty; 8]; 8],
+        };
+        game.castling_right = sol_chess::CastlingRight {
+            white_kingside: false,
+            white_queenside: false,
+            black_kingside: false,
+            black_queenside: false,
+        };
+        for &(piece, algebraic) in pieces {
+            game.board.set_piece(piece, square(algebraic));
+        }
+        game
+    }
+
+    fn play(game: &mut sol_chess::Game, moves: &[&str]) {
+        for mv in moves {
+            let color = game.get_current_player_color().unwrap();
+            let (from, to) = parse_move(game, color, mv).unwrap();
+            game.move_piece(color, from, to);
+            game.next_turn().unwrap();
+        }
+    }
+
+    #[test]
+    fn parses_san_and_uci_alike() {
+        let game = start_game();
+        let parse = |input| parse_move(&game, Color::White, input);
+
+        assert_eq!(parse("e2e4"), Some((square("e2"), square("e4"))));
+        assert_eq!(parse("e4"), parse("e2e4"));
+        assert_eq!(parse("Nf3"), Some((square("g1"), square("f3"))));
+        assert_eq!(parse(" Nf3+ "), parse("g1f3"));
+        assert_eq!(parse("e5"), None);
+        assert_eq!(parse("Ke2"), None);
+        assert_eq!(parse("e2"), None);
+    }
+
+    #[test]
+    fn parses_castling() {
+        let mut game = start_game();
+        play(&mut game, &["e4", "e5", "Nf3", "Nc6", "Bc4", "d6"]);
+        assert_eq!(
+            parse_move(&game, Color::White, "O-O"),
+            Some((square("e1"), square("g1")))
+        );
+        assert_eq!(
+            parse_move(&game, Color::White, "0-0"),
+            parse_move(&game, Color::White, "O-O")
+        );
+        assert_eq!(parse_move(&game, Color::White, "O-O-O"), None);
+
+        let mut game = start_game();
+        play(
+            &mut game,
+            &["d4", "d5", "Nc3", "Nc6", "Bf4", "Bf5", "Qd2", "Qd7"],
+        );
+        assert_eq!(
+            parse_move(&game, Color::White, "O-O-O"),
+            Some((square("e1"), square("c1")))
+        );
+    }
+
+    #[test]
+    fn parses_queen_promotions_only_in_san() {
+        let game = create_game(&[
+            (Piece::WhiteKing, "a1"),
+            (Piece::WhitePawn, "e7"),
+            (Piece::BlackKing, "a8"),
+        ]);
+        let promotion = Some((square("e7"), square("e8")));
+
+        assert_eq!(parse_move(&game, Color::White, "e8=Q"), promotion);
+        assert_eq!(parse_move(&game, Color::White, "e7e8q"), promotion);
+        assert_eq!(parse_move(&game, Color::White, "e8=N"), None);
+        assert_eq!(parse_move(&game, Color::White, "e8"), None);
+    }
+
+    #[test]
+    fn disambiguates_pieces_reaching_the_same_square() {
+        let game = create_game(&[
+            (Piece::WhiteKing, "h1"),
+            (Piece::WhiteKnight, "b1"),
+            (Piece::WhiteKnight, "f3"),
+            (Piece::WhiteRook, "a1"),
+            (Piece::WhiteRook, "a5"),
+            (Piece::BlackKing, "h8"),
+        ]);
+        let parse = |input| parse_move(&game, Color::White, input);
+
+        assert_eq!(parse("Nbd2"), Some((square("b1"), square("d2"))));
+        assert_eq!(parse("Nfd2"), Some((square("f3"), square("d2"))));
+        assert_eq!(parse("Nd2"), None);
+        assert_eq!(parse("R1a3"), Some((square("a1"), square("a3"))));
+        assert_eq!(parse("R5a3"), Some((square("a5"), square("a3"))));
+        assert_eq!(parse("Ra3"), None);
+    }
+
+    #[test]
+    fn writes_the_fen_of_the_start_position() {
+        assert_eq!(
+            to_fen(&start_game()),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn writes_the_en_passant_square_until_it_is_taken() {
+        let mut game = start_game();
+        play(&mut game, &["e4"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+
+        play(&mut game, &["a6", "e5", "d5"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbqkbnr/1pp1pppp/p7/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3"
+        );
+
+        play(&mut game, &["exd6"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+        );
+    }
+
+    #[test]
+    fn writes_the_castling_rights_left() {
+        let mut game = start_game();
+        play(&mut game, &["Nf3", "Nf6", "Rg1"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbqkb1r/pppppppp/5n2/8/8/5N2/PPPPPPPP/RNBQKBR1 b Qkq - 0 2"
+        );
+
+        play(&mut game, &["e5", "e4", "Ke7"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbq1b1r/ppppkppp/5n2/4p3/4P3/5N2/PPPP1PPP/RNBQKBR1 w Q - 0 4"
+        );
+
+        play(&mut game, &["Ke2"]);
+        assert_eq!(
+            to_fen(&game),
+            "rnbq1b1r/ppppkppp/5n2/4p3/4P3/5N2/PPPPKPPP/RNBQ1BR1 b - - 0 4"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use {
     anchor_lang::prelude::*,
     clockwork_client::{Client, ClientResult},
-    solana_sdk::{instruction::Instruction, signature::Keypair, transaction::Transaction},
+    sol_chess_sdk::SdkResult,
+    solana_sdk::{instruction::Instruction, signature::Keypair},
 };
 
 pub fn send_and_confirm_tx(
@@ -9,33 +10,13 @@ pub fn send_and_confirm_tx(
     ix: Vec<Instruction>,
     signers: Option<Vec<&Keypair>>,
     label: String,
-) -> ClientResult<()> {
-    let mut tx;
-
-    match signers {
-        Some(signer_keypairs) => {
-            tx = Transaction::new_signed_with_payer(
-                &ix,
-                Some(&client.payer_pubkey()),
-                &signer_keypairs,
-                client.get_latest_blockhash().unwrap(),
-            );
-        }
-        None => {
-            tx = Transaction::new_with_payer(&ix, Some(&client.payer_pubkey()));
-        }
-    }
-
-    tx.sign(&[client.payer()], client.latest_blockhash().unwrap());
-
-    // Send and confirm initialize tx
-    match client.send_and_confirm_transaction(&tx) {
-        Ok(sig) => println!(
-            "{} tx: ✅ https://explorer.solana.com/tx/{}?cluster=custom",
-            label, sig
-        ),
-        Err(err) => println!("{} tx: ❌ {:#?}", label, err),
-    }
+) -> SdkResult<()> {
+    let signers = signers.unwrap_or_default();
+    let sig = sol_chess_sdk::send_and_confirm(client, client.payer(), &ix, &signers)?;
+    println!(
+        "{} tx: ✅ https://explorer.solana.com/tx/{}?cluster=custom",
+        label, sig
+    );
     Ok(())
 }
 
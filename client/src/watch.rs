@@ -0,0 +1,385 @@
+use {
+    crate::*,
+    serde_json::{json, Value},
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig},
+    solana_sdk::{account::Account, commitment_config::CommitmentConfig},
+};
+
+/// Something that happened in a game between two updates of its account.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameUpdate {
+    Started {
+        white: Pubkey,
+        black: Pubkey,
+    },
+    Move {
+        ply: u16,
+        color: sol_chess::Color,
+        from: sol_chess::Square,
+        to: sol_chess::Square,
+        san: String,
+        white_timer: u32,
+        black_timer: u32,
+    },
+    Takeback {
+        ply: u16,
+    },
+    // several plies landed in a single update, the position is still current
+    Resynced {
+        ply: u16,
+    },
+    DrawOffered {
+        color: sol_chess::Color,
+    },
+    Finished {
+        game_state: sol_chess::GameState,
+    },
+}
+
+impl GameUpdate {
+    pub fn to_json(&self) -> Value {
+        match self {
+            GameUpdate::Started { white, black } => json!({
+                "type": "started",
+                "white": white.to_string(),
+                "black": black.to_string(),
+            }),
+            GameUpdate::Move {
+                ply,
+                color,
+                from,
+                to,
+                san,
+                white_timer,
+                black_timer,
+            } => json!({
+                "type": "move",
+                "ply": ply,
+                "color": format!("{:?}", color),
+                "uci": to_uci_move(*from, *to),
+                "san": san,
+                "white_timer": white_timer,
+                "black_timer": black_timer,
+            }),
+            GameUpdate::Takeback { ply } => json!({ "type": "takeback", "ply": ply }),
+            GameUpdate::Resynced { ply } => json!({ "type": "resynced", "ply": ply }),
+            GameUpdate::DrawOffered { color } => json!({
+                "type": "draw_offered",
+                "color": format!("{:?}", color),
+            }),
+            GameUpdate::Finished { game_state } => json!({
+                "type": "finished",
+                "game_state": format!("{:?}", game_state),
+            }),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            GameUpdate::Started { white, black } => {
+                format!("game started, white {} black {}", white, black)
+            }
+            GameUpdate::Move {
+                ply,
+                color,
+                san,
+                white_timer,
+                black_timer,
+                ..
+            } => {
+                let move_number = ply.div_ceil(2);
+                let dots = if color.is_white() { "." } else { "..." };
+                format!(
+                    "{}{} {:<8} white {} black {}",
+                    move_number,
+                    dots,
+                    san,
+                    format_clock(*white_timer),
+                    format_clock(*black_timer)
+                )
+            }
+            GameUpdate::Takeback { ply } => format!("takeback, back to ply {}", ply),
+            GameUpdate::Resynced { ply } => format!("resynced on ply {}", ply),
+            GameUpdate::DrawOffered { color } => format!("{:?} offers a draw", color),
+            GameUpdate::Finished { game_state } => format!("game over: {:?}", game_state),
+        }
+    }
+}
+
+/// Finds the move played between two positions one ply apart by diffing their
+/// boards: the mover's piece leaves `from` and lands on `to`, and when the
+/// king castles the rook's squares are ignored.
+pub fn detect_move(
+    previous: &sol_chess::Board,
+    current: &sol_chess::Board,
+    color: sol_chess::Color,
+) -> Option<(sol_chess::Square, sol_chess::Square)> {
+    let is_mover = |piece: sol_chess::Piece| piece.is_color(color);
+
+    let mut from = vec![];
+    let mut to = vec![];
+    for rank in 0..8 {
+        for file in 0..8 {
+            let square = sol_chess::Square { rank, file };
+            let (before, after) = (previous.get_piece(square), current.get_piece(square));
+            if before == after {
+                continue;
+            }
+            if is_mover(before) && after.is_empty() {
+                from.push(square);
+            }
+            if is_mover(after) {
+                to.push(square);
+            }
+        }
+    }
+
+    let pick = |squares: Vec<sol_chess::Square>, board: &sol_chess::Board| match squares.len() {
+        1 => Some(squares[0]),
+        _ => squares
+            .into_iter()
+            .find(|&square| board.get_piece(square).is_king()),
+    };
+    Some((pick(from, previous)?, pick(to, current)?))
+}
+
+/// Updates between two successive states of the same game account.
+pub fn diff_games(previous: &sol_chess::Game, current: &sol_chess::Game) -> Vec<GameUpdate> {
+    let mut updates = vec![];
+
+    if previous.game_state.is_waiting() && !current.game_state.is_waiting() {
+        updates.push(GameUpdate::Started {
+            white: current.white.unwrap_or_default(),
+            black: current.black.unwrap_or_default(),
+        });
+    }
+
+    if current.ply == previous.ply + 1 {
+        let detected = previous.get_current_player_color().ok().and_then(|color| {
+            detect_move(&previous.board, &current.board, color).map(|(from, to)| (color, from, to))
+        });
+        match detected {
+            Some((color, from, to)) => updates.push(GameUpdate::Move {
+                ply: current.ply,
+                color,
+                from,
+                to,
+                san: previous.get_san(color, from, to),
+                white_timer: current.time_control.white_timer,
+                black_timer: current.time_control.black_timer,
+            }),
+            None => updates.push(GameUpdate::Resynced { ply: current.ply }),
+        }
+    } else if current.ply > previous.ply {
+        updates.push(GameUpdate::Resynced { ply: current.ply });
+    } else if current.ply < previous.ply {
+        updates.push(GameUpdate::Takeback { ply: current.ply });
+    }
+
+    if let Some(offer) = current.draw_state.get_offer() {
+        if previous.draw_state.get_offer() != Some(offer) {
+            updates.push(GameUpdate::DrawOffered { color: offer.color });
+        }
+    }
+
+    if !previous.is_finished() && current.is_finished() {
+        updates.push(GameUpdate::Finished {
+            game_state: current.game_state,
+        });
+    }
+
+    updates
+}
+
+fn print_update(update: &GameUpdate, as_json: bool) {
+    if as_json {
+        println!("{}", update.to_json());
+    } else {
+        println!("{}", update.describe());
+    }
+}
+
+/// Follows a game over a websocket subscription, printing every update until
+/// the game is over.
+pub fn watch(config: &CliConfig, client: &Client, game: Pubkey, as_json: bool) -> CommandResult {
+    let mut previous = sol_chess_sdk::fetch_game(client, game)?;
+    if as_json {
+        println!(
+            "{}",
+            json!({
+                "type": "snapshot",
+                "game": game.to_string(),
+                "ply": previous.ply,
+                "game_state": format!("{:?}", previous.game_state),
+            })
+        );
+    } else {
+        print_game(game, &previous);
+        println!();
+    }
+    if previous.is_finished() {
+        return Ok(());
+    }
+
+    let (mut subscription, receiver) = PubsubClient::account_subscribe(
+        &config.websocket_url,
+        &game,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..RpcAccountInfoConfig::default()
+        }),
+    )?;
+
+    for response in receiver.iter() {
+        let account = response
+            .value
+            .decode::<Account>()
+            .ok_or("failed to decode the game account")?;
+        let current = sol_chess_sdk::decode_account::<sol_chess::Game>(game, &account.data)?;
+
+        for update in diff_games(&previous, &current) {
+            print_update(&update, as_json);
+        }
+        if current.is_finished() {
+            break;
+        }
+        previous = current;
+    }
+
+    subscription.shutdown().ok();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        sol_chess::{Color, Piece, Square},
+    };
+
+    fn square(algebraic: &str) -> Square {
+        Square::from_algebraic(algebraic).unwrap()
+    }
+
+    fn start_game() -> sol_chess::Game {
+        let game_config = sol_chess::GameConfig {
+            timer: 600,
+            ..sol_chess::GameConfig::default()
+        };
+        let mut game = sol_chess::Game::create(game_config, 0, Pubkey::default(), 0, 0);
+        game.join_game(Pubkey::new_unique(), Color::White);
+        game.join_game(Pubkey::new_unique(), Color::Black);
+        game.start_game(0);
+        game
+    }
+
+    // plays `moves` the way `move_piece` does, ten seconds apart
+    fn play(game: &mut sol_chess::Game, moves: &[&str]) {
+        for mv in moves {
+            let color = game.get_current_player_color().unwrap();
+            let (from, to) = parse_move(game, color, mv).unwrap();
+            game.move_piece(color, from, to);
+            game.next_turn().unwrap();
+            game.reset_takeback_state();
+            game.update_time_control(color, game.ply as i64 * 10);
+        }
+    }
+
+    // the single update of playing `mv` after `moves`
+    fn play_update(moves: &[&str], mv: &str) -> GameUpdate {
+        let mut previous = start_game();
+        play(&mut previous, moves);
+        let mut current = previous.clone();
+        play(&mut current, &[mv]);
+
+        let mut updates = diff_games(&previous, &current);
+        assert_eq!(updates.len(), 1);
+        updates.remove(0)
+    }
+
+    #[test]
+    fn reports_a_move_with_the_clocks() {
+        let update = play_update(&["e4"], "e5");
+
+        assert_eq!(
+            update,
+            GameUpdate::Move {
+                ply: 2,
+                color: Color::Black,
+                from: square("e7"),
+                to: square("e5"),
+                san: "e5".to_string(),
+                white_timer: 600,
+                black_timer: 590,
+            }
+        );
+        assert_eq!(update.describe(), "1... e5       white 10:00 black 09:50");
+        assert_eq!(update.to_json()["uci"], "e7e5");
+    }
+
+    #[test]
+    fn reports_castling_as_the_king_move() {
+        let update = play_update(&["e4", "e5", "Nf3", "Nc6", "Bc4", "Nf6"], "O-O");
+
+        let GameUpdate::Move { from, to, san, .. } = &update else {
+            panic!("expected a move, got {:?}", update);
+        };
+        assert_eq!((*from, *to), (square("e1"), square("g1")));
+        assert_eq!(san, "O-O");
+        assert!(update.describe().starts_with("4. O-O "));
+    }
+
+    #[test]
+    fn reports_en_passant() {
+        let update = play_update(&["e4", "a6", "e5", "d5"], "exd6");
+
+        let GameUpdate::Move { from, to, san, .. } = &update else {
+            panic!("expected a move, got {:?}", update);
+        };
+        assert_eq!((*from, *to), (square("e5"), square("d6")));
+        assert_eq!(san, "exd6");
+        assert!(update.describe().starts_with("3. exd6 "));
+    }
+
+    #[test]
+    fn reports_a_promotion() {
+        let mut previous = start_game();
+        previous.board = sol_chess::Board {
+            board: [[Piece::Empty; 8]; 8],
+        };
+        for (piece, algebraic) in [
+            (Piece::WhiteKing, "a1"),
+            (Piece::WhitePawn, "e7"),
+            (Piece::BlackKing, "a8"),
+        ] {
+            previous.board.set_piece(piece, square(algebraic));
+        }
+        let mut current = previous.clone();
+        play(&mut current, &["e8=Q"]);
+        assert_eq!(current.board.get_piece(square("e8")), Piece::WhiteQueen);
+
+        let updates = diff_games(&previous, &current);
+
+        let [GameUpdate::Move { from, to, san, .. }] = updates.as_slice() else {
+            panic!("expected a move, got {:?}", updates);
+        };
+        assert_eq!((*from, *to), (square("e7"), square("e8")));
+        assert_eq!(san, "e8=Q");
+        assert!(updates[0].describe().starts_with("1. e8=Q "));
+    }
+
+    #[test]
+    fn reports_a_takeback() {
+        let mut previous = start_game();
+        play(&mut previous, &["e4", "e5"]);
+        let mut current = previous.clone();
+        current.take_back(Color::White, 30).unwrap();
+
+        let updates = diff_games(&previous, &current);
+
+        assert_eq!(updates, vec![GameUpdate::Takeback { ply: 0 }]);
+        assert_eq!(updates[0].describe(), "takeback, back to ply 0");
+    }
+}
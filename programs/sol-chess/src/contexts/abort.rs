@@ -0,0 +1,55 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Abort<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub adversary_user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> Abort<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            game,
+            adversary_user,
+            clock,
+            ..
+        } = self;
+
+        let color = game.get_player_color(user.key())?;
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(
+            game.get_adversary_player(color)?.eq(&adversary_user.key()),
+            CustomError::InvalidAdversaryUserAccount
+        );
+        require!(game.can_abort(), CustomError::CannotAbort);
+
+        // an aborted game is not rated and does not count towards stats
+        game.set_aborted();
+        user.remove_game(game.key());
+        adversary_user.remove_game(game.key());
+
+        if game.has_wager() {
+            user.increase_balance(game.get_wager());
+            adversary_user.increase_balance(game.get_wager());
+        }
+
+        emit!(GameAborted {
+            game: game.key(),
+            user: user.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptRematch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub adversary_user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(init,payer=payer,space=8 + size_of::<Game>(), seeds=[SEED_GAME,user.key().as_ref(),&user.games_created.to_be_bytes()], bump)]
+    pub rematch_game: Box<Account<'info, Game>>,
+    #[account(init,payer=payer,space=MoveLog::space(0), seeds=[SEED_MOVE_LOG,rematch_game.key().as_ref()], bump)]
+    pub move_log: Account<'info, MoveLog>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> AcceptRematch<'info> {
+    pub fn process(&mut self, rematch_game_bump: u8, move_log_bump: u8) -> Result<()> {
+        let Self {
+            user,
+            adversary_user,
+            game,
+            rematch_game,
+            move_log,
+            clock,
+            ..
+        } = self;
+
+        let color = game.get_player_color(user.key())?;
+
+        require!(game.is_finished(), CustomError::GameNotFinished);
+        require!(
+            game.get_adversary_player(color)?.eq(&adversary_user.key()),
+            CustomError::InvalidAdversaryUserAccount
+        );
+        require!(
+            game.has_offered_rematch(color.get_opposite()),
+            CustomError::NoRematchOffer
+        );
+        require!(
+            user.can_join_game() && adversary_user.can_join_game(),
+            CustomError::TooManyActiveGames
+        );
+
+        game.accept_rematch();
+
+        rematch_game.new(
+            game.game_config,
+            clock.unix_timestamp,
+            user.key(),
+            user.games_created,
+            rematch_game_bump,
+        )?;
+        move_log.new(rematch_game.key(), move_log_bump)?;
+        user.increment_games_created();
+
+        // colors are swapped from the finished game
+        rematch_game.join_game(user.key(), color.get_opposite());
+        rematch_game.join_game(adversary_user.key(), color);
+        rematch_game.start_game(clock.unix_timestamp);
+
+        emit!(RematchAccepted {
+            game: game.key(),
+            rematch_game: rematch_game.key(),
+            user: user.key(),
+        });
+        emit!(GameStarted {
+            game: rematch_game.key(),
+            white: rematch_game.white.unwrap(),
+            black: rematch_game.black.unwrap(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        user.add_game(rematch_game.key());
+        adversary_user.add_game(rematch_game.key());
+
+        if rematch_game.has_wager() {
+            let wager = rematch_game.get_wager();
+            require!(
+                user.has_sufficient(wager) && adversary_user.has_sufficient(wager),
+                CustomError::InsufficientBalance
+            );
+            user.decrease_balance(wager);
+            adversary_user.decrease_balance(wager);
+        }
+
+        Ok(())
+    }
+}
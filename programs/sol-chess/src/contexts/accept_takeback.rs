@@ -0,0 +1,54 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptTakeback<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+    #[account(mut,seeds=[SEED_MOVE_LOG,game.key().as_ref()],bump=move_log.bump,realloc=MoveLog::space(move_log.len() + 1),realloc::payer=payer,realloc::zero=false)]
+    pub move_log: Account<'info, MoveLog>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> AcceptTakeback<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            game,
+            move_log,
+            clock,
+            ..
+        } = self;
+
+        let adversary_color = game.get_player_color(user.key())?.get_opposite();
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(
+            game.has_requested_takeback(adversary_color),
+            CustomError::NoTakebackRequested
+        );
+        require!(
+            game.can_take_back(adversary_color)?,
+            CustomError::NothingToTakeBack
+        );
+
+        let plies = game.get_takeback_plies(adversary_color)?;
+        game.take_back(adversary_color, clock.unix_timestamp)?;
+        move_log.log_takeback(plies as u16, clock.unix_timestamp);
+
+        emit!(TakebackAccepted {
+            game: game.key(),
+            user: user.key(),
+            ply: game.ply,
+        });
+
+        Ok(())
+    }
+}
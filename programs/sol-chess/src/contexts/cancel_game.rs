@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+    #[account(mut,close=payer,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+    #[account(mut,close=payer,seeds=[SEED_MOVE_LOG,game.key().as_ref()],bump=move_log.bump)]
+    pub move_log: Account<'info, MoveLog>,
+    #[account(mut,address=LobbyPage::pda(game.lobby_page).0)]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
+}
+
+impl<'info> CancelGame<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            game,
+            lobby_page,
+            ..
+        } = self;
+
+        require!(game.owner == user.key(), CustomError::NotGameOwner);
+        require!(game.is_not_started(), CustomError::GameAlreadyStarted);
+        require!(
+            game.has_no_opponent(user.key()),
+            CustomError::OpponentAlreadyJoined
+        );
+
+        if game.is_in_game(user.key()) {
+            user.remove_game(game.key());
+            if game.has_wager() {
+                user.increase_balance(game.get_wager());
+            }
+        }
+
+        lobby_page.remove_seek(game.key());
+
+        emit!(GameCancelled {
+            game: game.key(),
+            user: user.key(),
+        });
+
+        Ok(())
+    }
+}
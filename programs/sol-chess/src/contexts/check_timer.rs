@@ -0,0 +1,50 @@
+use crate::*;
+
+/// Settles a game whose player to move has run out of time. Anyone can crank
+/// it, a clockwork thread included.
+#[derive(Accounts)]
+pub struct CheckTimer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // the player to move and their adversary
+    #[account(mut,address=game.get_current_player_pubkey()?)]
+    pub user: Account<'info, User>,
+    #[account(mut,address=game.get_adversary_player(game.get_current_player_color()?)?)]
+    pub adversary_user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> CheckTimer<'info> {
+    pub fn process(&mut self, leaderboard: Option<&AccountInfo>) -> Result<()> {
+        let Self {
+            user,
+            game,
+            adversary_user,
+            clock,
+            ..
+        } = self;
+
+        let color = game.get_current_player_color()?;
+
+        if game.has_no_time(color, clock.unix_timestamp) {
+            emit!(GameTimedOut {
+                game: game.key(),
+                user: user.key(),
+                color,
+            });
+            game.settle(
+                user,
+                adversary_user,
+                leaderboard,
+                Outcome::Win(color.get_opposite(), Termination::Timeout),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ClaimAbandonment<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub adversary_user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> ClaimAbandonment<'info> {
+    pub fn process(&mut self, leaderboard: Option<&AccountInfo>, claim_win: bool) -> Result<()> {
+        let Self {
+            user,
+            game,
+            adversary_user,
+            clock,
+            ..
+        } = self;
+
+        let color = game.get_player_color(user.key())?;
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(
+            game.get_adversary_player(color)?.eq(&adversary_user.key()),
+            CustomError::InvalidAdversaryUserAccount
+        );
+        require!(
+            game.get_current_player_color()? != color && game.is_inactive(clock.unix_timestamp),
+            CustomError::PlayerNotInactive
+        );
+        require!(
+            !claim_win || game.ply >= ABANDONMENT_MIN_PLY,
+            CustomError::TooFewMovesToClaimWin
+        );
+
+        emit!(AbandonmentClaimed {
+            game: game.key(),
+            user: user.key(),
+            claim_win,
+            ply: game.ply,
+        });
+
+        if claim_win {
+            return game.settle(
+                user,
+                adversary_user,
+                leaderboard,
+                Outcome::Win(color, Termination::Abandonment),
+                clock.unix_timestamp,
+            );
+        }
+
+        // no contest, settled like an abort
+        user.remove_game(game.key());
+        adversary_user.remove_game(game.key());
+        game.set_aborted();
+        if game.has_wager() {
+            user.increase_balance(game.get_wager());
+            adversary_user.increase_balance(game.get_wager());
+        }
+        emit!(GameAborted {
+            game: game.key(),
+            user: user.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
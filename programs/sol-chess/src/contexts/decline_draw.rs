@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct DeclineDraw<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+}
+
+impl<'info> DeclineDraw<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self { user, game, .. } = self;
+
+        let adversary_color = game.get_player_color(user.key())?.get_opposite();
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(
+            game.has_offered_draw(adversary_color),
+            CustomError::NoDrawOffer
+        );
+
+        game.decline_draw();
+
+        emit!(DrawDeclined {
+            game: game.key(),
+            user: user.key(),
+        });
+
+        Ok(())
+    }
+}
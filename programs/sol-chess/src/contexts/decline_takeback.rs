@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct DeclineTakeback<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+}
+
+impl<'info> DeclineTakeback<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self { user, game, .. } = self;
+
+        let adversary_color = game.get_player_color(user.key())?.get_opposite();
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(
+            game.has_requested_takeback(adversary_color),
+            CustomError::NoTakebackRequested
+        );
+
+        game.reset_takeback_state();
+
+        emit!(TakebackDeclined {
+            game: game.key(),
+            user: user.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Dequeue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=MatchmakingQueue::pda(&matchmaking_queue.game_config).0)]
+    pub matchmaking_queue: Box<Account<'info, MatchmakingQueue>>,
+}
+
+impl<'info> Dequeue<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            matchmaking_queue,
+            ..
+        } = self;
+
+        require!(
+            matchmaking_queue.is_queued(user.key()),
+            CustomError::NotInQueue
+        );
+
+        matchmaking_queue.dequeue(user.key());
+
+        if matchmaking_queue.has_wager() {
+            user.increase_balance(matchmaking_queue.get_wager());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Enqueue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=MatchmakingQueue::pda(&matchmaking_queue.game_config).0)]
+    pub matchmaking_queue: Box<Account<'info, MatchmakingQueue>>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> Enqueue<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            matchmaking_queue,
+            clock,
+            ..
+        } = self;
+
+        require!(
+            matchmaking_queue.is_not_queued(user.key()),
+            CustomError::AlreadyInQueue
+        );
+        require!(!matchmaking_queue.is_full(), CustomError::QueueFull);
+        require!(user.can_join_game(), CustomError::TooManyActiveGames);
+
+        // the wager is escrowed while waiting so a match can always start
+        if matchmaking_queue.has_wager() {
+            let wager = matchmaking_queue.get_wager();
+            require!(user.has_sufficient(wager), CustomError::InsufficientBalance);
+            user.decrease_balance(wager);
+        }
+
+        let elo = user.get_elo(matchmaking_queue.get_time_category());
+        matchmaking_queue.enqueue(user.key(), elo, clock.unix_timestamp);
+
+        Ok(())
+    }
+}
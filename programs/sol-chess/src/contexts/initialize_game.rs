@@ -7,27 +7,81 @@ pub struct InitializeGame<'info> {
 
     #[account(mut,address=User::pda(payer.key()).0)]
     pub user: Account<'info, User>,
-    #[account(init,payer=payer,space=8 + size_of::<Game>(), seeds=[SEED_GAME,user.key().as_ref(),&user.games.to_be_bytes()], bump)]
+    #[account(init,payer=payer,space=8 + size_of::<Game>(), seeds=[SEED_GAME,user.key().as_ref(),&user.games_created.to_be_bytes()], bump)]
     pub game: Account<'info, Game>,
+    #[account(init,payer=payer,space=MoveLog::space(0), seeds=[SEED_MOVE_LOG,game.key().as_ref()], bump)]
+    pub move_log: Account<'info, MoveLog>,
+    #[account(mut,address=LobbyPage::pda(lobby_page.page).0)]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
 
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> InitializeGame<'info> {
-    pub fn process(&mut self, game_config: GameConfig, game_bump: u8) -> Result<()> {
+    pub fn process(
+        &mut self,
+        game_config: GameConfig,
+        rating_range: Option<RatingRange>,
+        invite: Option<Invite>,
+        game_bump: u8,
+        move_log_bump: u8,
+    ) -> Result<()> {
         let InitializeGame {
-            game, user, clock, ..
+            game,
+            move_log,
+            user,
+            lobby_page,
+            clock,
+            ..
         } = self;
 
+        require!(user.can_join_game(), CustomError::TooManyActiveGames);
+        if let Some(rating_range) = rating_range {
+            require!(rating_range.is_valid(), CustomError::InvalidRatingRange);
+        }
+
         game.new(
             game_config,
             clock.unix_timestamp,
             user.key(),
-            user.games,
+            user.games_created,
             game_bump,
         )?;
-        user.increment_games();
+        move_log.new(game.key(), move_log_bump)?;
+        user.increment_games_created();
+
+        emit!(GameCreated {
+            game: game.key(),
+            owner: user.key(),
+            game_config,
+            rating_range,
+            is_private: invite.is_some(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        let color = game_config.get_color_preference().get_creator_color();
+        game.join_game(user.key(), color);
+        emit!(GameJoined {
+            game: game.key(),
+            user: user.key(),
+            color,
+        });
+        user.add_game(game.key());
+        if game.has_wager() {
+            let wager = game.get_wager();
+            require!(user.has_sufficient(wager), CustomError::InsufficientBalance);
+            user.decrease_balance(wager);
+        }
+
+        // private games are only reachable through their invite
+        match invite {
+            Some(invite) => game.set_invite(invite)?,
+            None => {
+                require!(!lobby_page.is_full(), CustomError::LobbyPageFull);
+                game.list_in_lobby(lobby_page, rating_range)?;
+            }
+        }
         Ok(())
     }
 }
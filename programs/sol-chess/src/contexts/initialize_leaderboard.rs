@@ -0,0 +1,21 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(category: TimeCategory)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init,payer=payer,space=8 + size_of::<Leaderboard>(), seeds=[SEED_LEADERBOARD,&[category.get_index() as u8]], bump)]
+    pub leaderboard: Box<Account<'info, Leaderboard>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeLeaderboard<'info> {
+    pub fn process(&mut self, category: TimeCategory, leaderboard_bump: u8) -> Result<()> {
+        let Self { leaderboard, .. } = self;
+        leaderboard.new(category, leaderboard_bump)?;
+        Ok(())
+    }
+}
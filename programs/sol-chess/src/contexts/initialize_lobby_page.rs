@@ -0,0 +1,21 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct InitializeLobbyPage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init,payer=payer,space=8 + size_of::<LobbyPage>(), seeds=[SEED_LOBBY_PAGE,&page.to_be_bytes()], bump)]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeLobbyPage<'info> {
+    pub fn process(&mut self, page: u32, lobby_page_bump: u8) -> Result<()> {
+        let Self { lobby_page, .. } = self;
+        lobby_page.new(page, lobby_page_bump)?;
+        Ok(())
+    }
+}
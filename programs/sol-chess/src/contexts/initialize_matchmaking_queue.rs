@@ -0,0 +1,30 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(game_config: GameConfig)]
+pub struct InitializeMatchmakingQueue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init,payer=payer,space=8 + size_of::<MatchmakingQueue>(), seeds=[SEED_MATCHMAKING_QUEUE,&MatchmakingQueue::get_key(&game_config)], bump)]
+    pub matchmaking_queue: Box<Account<'info, MatchmakingQueue>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMatchmakingQueue<'info> {
+    pub fn process(&mut self, game_config: GameConfig, queue_bump: u8) -> Result<()> {
+        let Self {
+            matchmaking_queue, ..
+        } = self;
+
+        // colors go to the longest waiting player, not to a preference
+        require!(
+            game_config.get_color_preference().is_random(),
+            CustomError::InvalidQueueConfig
+        );
+
+        matchmaking_queue.new(game_config, queue_bump)?;
+        Ok(())
+    }
+}
@@ -13,8 +13,13 @@ pub struct InitializeUser<'info> {
 
 impl<'info> InitializeUser<'info> {
     pub fn process(&mut self) -> Result<()> {
-        let InitializeUser { user, .. } = self;
+        let InitializeUser { user, payer, .. } = self;
         user.new()?;
+
+        emit!(UserInitialized {
+            user: user.key(),
+            authority: payer.key(),
+        });
         Ok(())
     }
 }
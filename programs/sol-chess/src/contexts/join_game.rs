@@ -1,4 +1,5 @@
 use crate::*;
+use anchor_lang::solana_program::{hash::hashv, sysvar::slot_hashes};
 
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
@@ -10,20 +11,81 @@ pub struct JoinGame<'info> {
 
     #[account(mut,address=Game::pda(game.owner,game.id).0)]
     pub game: Account<'info, Game>,
+    #[account(mut,address=LobbyPage::pda(game.lobby_page).0)]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
+
+    /// CHECK: only the most recent slot hash is read, as the seed of random colors
+    #[account(address=slot_hashes::ID)]
+    pub recent_slothashes: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> JoinGame<'info> {
-    pub fn process(&mut self, color: Color) -> Result<()> {
-        let Self { user, game, .. } = self;
+    pub fn process(&mut self, invite_code: Option<String>) -> Result<()> {
+        let Self {
+            user,
+            game,
+            lobby_page,
+            recent_slothashes,
+            clock,
+            ..
+        } = self;
+
+        let color = game.get_open_color();
+        require!(color.is_some(), CustomError::ColorNotAvailable);
+        require!(
+            user.key() == game.owner
+                || game.is_in_rating_range(user.get_elo(game.get_time_category())),
+            CustomError::RatingOutOfRange
+        );
+        require!(
+            user.key() == game.owner || game.is_invited(user.key(), invite_code.as_deref()),
+            CustomError::NotInvited
+        );
+        require!(game.is_not_in_game(user.key()), CustomError::AlreadyInGame);
+
+        require!(
+            user.has_game(game.key()) || user.can_join_game(),
+            CustomError::TooManyActiveGames
+        );
 
-        require!(game.color_available(color), CustomError::ColorNotAvailable);
-        // require!(game.is_not_in_game(user.key()), CustomError::AlreadyInGame);
+        user.add_game(game.key());
+        game.join_game(user.key(), color.unwrap());
+
+        if game.has_random_colors() {
+            // not unpredictable: the slot hash, the timestamp and both keys are
+            // all readable on chain while the join executes, so a joiner that
+            // wraps the join in a program of their own, or follows it with an
+            // instruction checking the result, can revert until they get the
+            // color they want. Random colors only protect against joiners who
+            // do not bother; wagered games that need fairness should fix colors.
+            let recent_slothash = &recent_slothashes.try_borrow_data()?[8..48];
+            let entropy = hashv(&[
+                recent_slothash,
+                &clock.unix_timestamp.to_be_bytes(),
+                user.key().as_ref(),
+                game.key().as_ref(),
+            ]);
+            game.assign_random_colors(entropy.to_bytes());
+        }
 
-        user.set_game(game.key());
-        game.join_game(user.key(), color);
+        emit!(GameJoined {
+            game: game.key(),
+            user: user.key(),
+            color: game.get_player_color(user.key())?,
+        });
 
         if game.is_full() {
-            game.start_game()
+            game.start_game(clock.unix_timestamp);
+            emit!(GameStarted {
+                game: game.key(),
+                white: game.white.unwrap(),
+                black: game.black.unwrap(),
+                timestamp: clock.unix_timestamp,
+            });
+            lobby_page.remove_seek(game.key());
+        } else {
+            lobby_page.update_seek_color(game.key(), game.get_seek_color());
         }
 
         if game.has_wager() {
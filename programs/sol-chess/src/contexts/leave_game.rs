@@ -9,22 +9,38 @@ pub struct LeaveGame<'info> {
     pub user: Account<'info, User>,
     #[account(mut,address=Game::pda(game.owner,game.id).0)]
     pub game: Account<'info, Game>,
+    #[account(mut,address=LobbyPage::pda(game.lobby_page).0)]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
 }
 
 impl<'info> LeaveGame<'info> {
     pub fn process(&mut self) -> Result<()> {
-        let Self { user, game, .. } = self;
+        let Self {
+            user,
+            game,
+            lobby_page,
+            ..
+        } = self;
 
         require!(game.is_not_started(), CustomError::GameAlreadyStarted);
-        require!(game.is_in_game(user.key()), CustomError::NotInGame);
-
-        let color = game.get_player_color(user.key());
+        let color = game.get_player_color(user.key())?;
         game.leave_game(color);
+        user.remove_game(game.key());
+        if game.is_empty() {
+            lobby_page.remove_seek(game.key());
+        } else {
+            lobby_page.update_seek_color(game.key(), game.get_seek_color());
+        }
 
         if game.has_wager() {
             user.increase_balance(game.get_wager())
         }
 
+        emit!(GameLeft {
+            game: game.key(),
+            user: user.key(),
+        });
+
         Ok(())
     }
 }
@@ -0,0 +1,102 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MatchPlayers<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub adversary_user: Account<'info, User>,
+
+    #[account(mut,address=MatchmakingQueue::pda(&matchmaking_queue.game_config).0)]
+    pub matchmaking_queue: Box<Account<'info, MatchmakingQueue>>,
+    #[account(init,payer=payer,space=8 + size_of::<Game>(), seeds=[SEED_GAME,user.key().as_ref(),&user.games_created.to_be_bytes()], bump)]
+    pub game: Account<'info, Game>,
+    #[account(init,payer=payer,space=MoveLog::space(0), seeds=[SEED_MOVE_LOG,game.key().as_ref()], bump)]
+    pub move_log: Account<'info, MoveLog>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> MatchPlayers<'info> {
+    pub fn process(&mut self, game_bump: u8, move_log_bump: u8) -> Result<()> {
+        let Self {
+            user,
+            adversary_user,
+            matchmaking_queue,
+            game,
+            move_log,
+            clock,
+            ..
+        } = self;
+
+        require!(
+            user.key() != adversary_user.key(),
+            CustomError::InvalidAdversaryUserAccount
+        );
+
+        let entry = matchmaking_queue.get_entry(user.key());
+        let adversary_entry = matchmaking_queue.get_entry(adversary_user.key());
+        require!(
+            entry.is_some() && adversary_entry.is_some(),
+            CustomError::NotInQueue
+        );
+
+        let entry = entry.unwrap();
+        let adversary_entry = adversary_entry.unwrap();
+        require!(
+            entry.is_compatible(&adversary_entry, clock.unix_timestamp),
+            CustomError::RatingOutOfRange
+        );
+        require!(
+            user.can_join_game() && adversary_user.can_join_game(),
+            CustomError::TooManyActiveGames
+        );
+
+        matchmaking_queue.dequeue(user.key());
+        matchmaking_queue.dequeue(adversary_user.key());
+
+        game.new(
+            matchmaking_queue.game_config,
+            clock.unix_timestamp,
+            user.key(),
+            user.games_created,
+            game_bump,
+        )?;
+        move_log.new(game.key(), move_log_bump)?;
+        user.increment_games_created();
+
+        // the player who waited longer plays white
+        if entry.enqueued_at <= adversary_entry.enqueued_at {
+            game.join_game(user.key(), Color::White);
+            game.join_game(adversary_user.key(), Color::Black);
+        } else {
+            game.join_game(adversary_user.key(), Color::White);
+            game.join_game(user.key(), Color::Black);
+        }
+        game.start_game(clock.unix_timestamp);
+
+        emit!(GameCreated {
+            game: game.key(),
+            owner: user.key(),
+            game_config: game.game_config,
+            rating_range: None,
+            is_private: false,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(GameStarted {
+            game: game.key(),
+            white: game.white.unwrap(),
+            black: game.black.unwrap(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        user.add_game(game.key());
+        adversary_user.add_game(game.key());
+
+        Ok(())
+    }
+}
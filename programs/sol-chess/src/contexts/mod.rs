@@ -1,21 +1,53 @@
-pub mod clockwork_check_timer;
+pub mod abort;
+pub mod accept_rematch;
+pub mod accept_takeback;
+pub mod cancel_game;
+pub mod check_timer;
+pub mod claim_abandonment;
+pub mod decline_draw;
+pub mod decline_takeback;
 pub mod deposit;
+pub mod dequeue;
+pub mod enqueue;
 pub mod initialize_game;
+pub mod initialize_leaderboard;
+pub mod initialize_lobby_page;
+pub mod initialize_matchmaking_queue;
 pub mod initialize_user;
 pub mod join_game;
 pub mod leave_game;
+pub mod match_players;
 pub mod move_piece;
 pub mod offer_draw;
+pub mod offer_rematch;
+pub mod request_takeback;
 pub mod resign;
+pub mod set_max_active_games;
 pub mod withdraw;
 
-pub use clockwork_check_timer::*;
+pub use abort::*;
+pub use accept_rematch::*;
+pub use accept_takeback::*;
+pub use cancel_game::*;
+pub use check_timer::*;
+pub use claim_abandonment::*;
+pub use decline_draw::*;
+pub use decline_takeback::*;
 pub use deposit::*;
+pub use dequeue::*;
+pub use enqueue::*;
 pub use initialize_game::*;
+pub use initialize_leaderboard::*;
+pub use initialize_lobby_page::*;
+pub use initialize_matchmaking_queue::*;
 pub use initialize_user::*;
 pub use join_game::*;
 pub use leave_game::*;
+pub use match_players::*;
 pub use move_piece::*;
 pub use offer_draw::*;
+pub use offer_rematch::*;
+pub use request_takeback::*;
 pub use resign::*;
+pub use set_max_active_games::*;
 pub use withdraw::*;
@@ -12,19 +12,31 @@ pub struct MovePiece<'info> {
 
     #[account(mut, address=Game::pda(game.owner,game.id).0)]
     pub game: Account<'info, Game>,
+    #[account(mut,seeds=[SEED_MOVE_LOG,game.key().as_ref()],bump=move_log.bump,realloc=MoveLog::space(move_log.len() + 1),realloc::payer=payer,realloc::zero=false)]
+    pub move_log: Account<'info, MoveLog>,
+
+    pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> MovePiece<'info> {
-    pub fn process(&mut self, from: Square, to: Square) -> Result<()> {
+    pub fn process(
+        &mut self,
+        leaderboard: Option<&AccountInfo>,
+        from: Square,
+        to: Square,
+    ) -> Result<()> {
         let Self {
             user,
             game,
+            move_log,
             adversary_user,
             clock,
             ..
         } = self;
-        let color = game.get_current_player_color();
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        let color = game.get_current_player_color()?;
 
         require!(
             game.has_time(color, clock.unix_timestamp),
@@ -32,12 +44,12 @@ impl<'info> MovePiece<'info> {
         );
 
         require!(
-            user.key() == game.get_current_player_pubkey(),
+            user.key() == game.get_current_player_pubkey()?,
             CustomError::NotUsersTurn
         );
 
         require!(
-            game.get_adversary_player(color).eq(&adversary_user.key()),
+            game.get_adversary_player(color)?.eq(&adversary_user.key()),
             CustomError::InvalidAdversaryUserAccount
         );
 
@@ -46,27 +58,64 @@ impl<'info> MovePiece<'info> {
             CustomError::InvalidMove
         );
 
+        let mut san = game.get_san(color, from, to);
         game.move_piece(color, from, to);
 
-        require!(game.not_in_check(color), CustomError::KingInCheck);
-
-        game.next_turn();
+        require!(game.not_in_check(color)?, CustomError::KingInCheck);
 
-        game.reset_draw_state();
+        game.next_turn()?;
 
-        if game.in_checkmate(color.get_opposite()) {
-            game.set_winner(color);
-            if game.has_wager() {
-                user.increase_balance(game.get_wager() * 2)
-            }
+        game.expire_draw_offer(color);
+        game.reset_takeback_state();
+        game.update_time_control(color, clock.unix_timestamp);
 
-            if game.is_rated() {
-                user.won_against(adversary_user.get_elo());
-                adversary_user.lost_against(user.get_elo());
-            }
+        let is_check = game.in_check(color.get_opposite())?;
+        let is_checkmate = game.in_checkmate(color.get_opposite())?;
+        if is_checkmate {
+            san.push('#');
+        } else if is_check {
+            san.push('+');
         }
 
-        game.update_time_control(color, clock.unix_timestamp);
+        let last_move = game
+            .get_last_move()
+            .ok_or_else(|| error!(CustomError::InvalidGameState))?;
+        let promotion = if last_move.piece.is_pawn() && to.is_last_rank(color) {
+            Some(color.get_queen())
+        } else {
+            None
+        };
+        move_log.log_move(from, to, promotion, clock.unix_timestamp);
+
+        emit!(MoveMade {
+            game: game.key(),
+            user: user.key(),
+            color,
+            ply: game.ply,
+            from,
+            to,
+            promotion,
+            capture: if last_move.captured.is_not_empty() {
+                Some(last_move.captured)
+            } else {
+                None
+            },
+            is_check,
+            san,
+            white_timer: game.time_control.white_timer,
+            black_timer: game.time_control.black_timer,
+            timestamp: clock.unix_timestamp,
+        });
+
+        if is_checkmate {
+            game.settle(
+                user,
+                adversary_user,
+                leaderboard,
+                Outcome::Win(color, Termination::Checkmate),
+                clock.unix_timestamp,
+            )?;
+        }
 
         Ok(())
     }
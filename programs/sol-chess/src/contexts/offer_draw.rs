@@ -12,43 +12,51 @@ pub struct OfferDraw<'info> {
 
     #[account(mut,address=Game::pda(game.owner,game.id).0)]
     pub game: Account<'info, Game>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> OfferDraw<'info> {
-    pub fn process(&mut self) -> Result<()> {
+    pub fn process(&mut self, leaderboard: Option<&AccountInfo>) -> Result<()> {
         let Self {
             user,
             game,
             adversary_user,
+            clock,
             ..
         } = self;
 
-        let color = game.get_player_color(user.key());
+        let color = game.get_player_color(user.key())?;
 
-        require!(game.is_in_game(user.key()), CustomError::NotInGame);
         require!(game.is_still_going(), CustomError::InvalidGameState);
         require!(
-            game.get_adversary_player(color).eq(&adversary_user.key()),
+            game.get_adversary_player(color)?.eq(&adversary_user.key()),
             CustomError::InvalidAdversaryUserAccount
         );
         require!(
             game.has_not_offered_draw(color),
             CustomError::AlreadyOfferedDraw
         );
+        require!(
+            game.has_offered_draw(color.get_opposite()) || game.can_offer_draw(color),
+            CustomError::DrawOfferCooldown
+        );
 
         game.update_draw_state(color);
         if game.is_draw() {
-            game.set_draw();
-
-            if game.has_wager() {
-                user.increase_balance(game.get_wager());
-                adversary_user.increase_balance(game.get_wager());
-            }
-
-            if game.is_rated() {
-                user.draw_against(adversary_user.get_elo());
-                adversary_user.draw_against(user.get_elo());
-            }
+            game.settle(
+                user,
+                adversary_user,
+                leaderboard,
+                Outcome::Draw,
+                clock.unix_timestamp,
+            )?;
+        } else {
+            emit!(DrawOffered {
+                game: game.key(),
+                user: user.key(),
+                color,
+                ply: game.ply,
+            });
         }
 
         Ok(())
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct OfferRematch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+}
+
+impl<'info> OfferRematch<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self { user, game, .. } = self;
+
+        let color = game.get_player_color(user.key())?;
+
+        require!(game.is_finished(), CustomError::GameNotFinished);
+        require!(
+            game.can_offer_rematch(color),
+            CustomError::AlreadyOfferedRematch
+        );
+
+        game.offer_rematch(color);
+
+        emit!(RematchOffered {
+            game: game.key(),
+            user: user.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RequestTakeback<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+
+    #[account(mut,address=Game::pda(game.owner,game.id).0)]
+    pub game: Account<'info, Game>,
+}
+
+impl<'info> RequestTakeback<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self { user, game, .. } = self;
+
+        let color = game.get_player_color(user.key())?;
+
+        require!(game.is_still_going(), CustomError::InvalidGameState);
+        require!(game.allows_takebacks(), CustomError::TakebacksNotAllowed);
+        require!(
+            !game.has_requested_takeback(color),
+            CustomError::AlreadyRequestedTakeback
+        );
+        require!(game.can_take_back(color)?, CustomError::NothingToTakeBack);
+
+        game.request_takeback(color);
+
+        emit!(TakebackRequested {
+            game: game.key(),
+            user: user.key(),
+            color,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetMaxActiveGames<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut,address=User::pda(payer.key()).0)]
+    pub user: Account<'info, User>,
+}
+
+impl<'info> SetMaxActiveGames<'info> {
+    pub fn process(&mut self, max_active_games: u8) -> Result<()> {
+        let Self { user, .. } = self;
+
+        require!(
+            max_active_games > 0 && max_active_games as usize <= MAX_ACTIVE_GAMES,
+            CustomError::InvalidMaxActiveGames
+        );
+
+        user.set_max_active_games(max_active_games);
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::*;
+
+// centipawns lost for a move to count as a blunder
+pub const DEFAULT_BLUNDER_THRESHOLD: i32 = 200;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Blunder {
+    // ply the move was played on, starting at 1
+    pub ply: u16,
+    pub color: Color,
+    pub played: (Square, Square),
+    pub best: (Square, Square),
+    // centipawns lost against the best move
+    pub loss: i32,
+}
+
+/// Best move for the side to move.
+pub fn get_hint(game: &Game, limits: SearchLimits) -> Result<Option<(Square, Square)>> {
+    Ok(search(game, limits)?.best_move)
+}
+
+/// Replays a finished game from its move log and searches every position,
+/// reporting the moves that lost at least `threshold` centipawns. Moves later
+/// taken back are reviewed too.
+pub fn find_blunders(
+    move_log: &MoveLog,
+    game_config: GameConfig,
+    limits: SearchLimits,
+    threshold: i32,
+) -> Result<Vec<Blunder>> {
+    let positions = move_log.replay(game_config)?;
+    let mut blunders = vec![];
+
+    for (index, logged_move) in move_log.decode()?.into_iter().enumerate() {
+        let (from, to) = match logged_move {
+            LoggedMove::Move { from, to, .. } => (from, to),
+            LoggedMove::Takeback { .. } => continue,
+        };
+        let (before, after) = (&positions[index], &positions[index + 1]);
+
+        let best = search(before, limits)?;
+        let best_move = match best.best_move {
+            Some(best_move) if best_move != (from, to) => best_move,
+            _ => continue,
+        };
+        let loss = best.score + search(after, limits)?.score;
+        if loss >= threshold {
+            blunders.push(Blunder {
+                ply: after.ply,
+                color: before.get_current_player_color()?,
+                played: (from, to),
+                best: best_move,
+                loss,
+            });
+        }
+    }
+
+    Ok(blunders)
+}
@@ -0,0 +1,145 @@
+use crate::*;
+
+pub const PAWN_VALUE: i32 = 100;
+pub const KNIGHT_VALUE: i32 = 320;
+pub const BISHOP_VALUE: i32 = 330;
+pub const ROOK_VALUE: i32 = 500;
+pub const QUEEN_VALUE: i32 = 900;
+pub const KING_VALUE: i32 = 20_000;
+
+// piece-square tables seen from white, 8th rank first like the board
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const KING_TABLE: [i32; 64] = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+pub fn get_piece_value(piece: Piece) -> i32 {
+    if piece.is_empty() {
+        0
+    } else if piece.is_pawn() {
+        PAWN_VALUE
+    } else if piece.is_knight() {
+        KNIGHT_VALUE
+    } else if piece.is_bishop() {
+        BISHOP_VALUE
+    } else if piece.is_rook() {
+        ROOK_VALUE
+    } else if piece.is_queen() {
+        QUEEN_VALUE
+    } else {
+        KING_VALUE
+    }
+}
+
+fn get_square_value(piece: Piece, square: Square) -> i32 {
+    // black reads the white tables upside down
+    let rank = if piece.is_white() {
+        square.get_rank()
+    } else {
+        7 - square.get_rank()
+    };
+    let index = rank * 8 + square.get_file();
+
+    if piece.is_pawn() {
+        PAWN_TABLE[index]
+    } else if piece.is_knight() {
+        KNIGHT_TABLE[index]
+    } else if piece.is_bishop() {
+        BISHOP_TABLE[index]
+    } else if piece.is_rook() {
+        ROOK_TABLE[index]
+    } else if piece.is_queen() {
+        QUEEN_TABLE[index]
+    } else {
+        KING_TABLE[index]
+    }
+}
+
+/// Material and piece-square score in centipawns, positive when white is
+/// better.
+pub fn evaluate(board: &Board) -> i32 {
+    let score = |color: Color| -> i32 {
+        board
+            .get_color_pieces(color)
+            .into_iter()
+            .map(|(piece, square)| get_piece_value(piece) + get_square_value(piece, square))
+            .sum()
+    };
+    score(Color::White) - score(Color::Black)
+}
+
+/// `evaluate` from the side of `color`.
+pub fn evaluate_for(board: &Board, color: Color) -> i32 {
+    if color.is_white() {
+        evaluate(board)
+    } else {
+        -evaluate(board)
+    }
+}
@@ -0,0 +1,10 @@
+//! Off-chain search built on the on-chain move generator, for hints, practice
+//! bots and reviewing finished games. Only compiled with the `engine` feature.
+
+pub mod analysis;
+pub mod evaluation;
+pub mod search;
+
+pub use analysis::*;
+pub use evaluation::*;
+pub use search::*;
@@ -0,0 +1,287 @@
+use {
+    crate::*,
+    std::time::{Duration, Instant},
+};
+
+pub const MATE_SCORE: i32 = 1_000_000;
+// captures searched past the horizon so a hanging piece is not missed
+const QUIESCENCE_DEPTH: u8 = 4;
+// nodes between two looks at the clock, a power of two
+const TIME_CHECK_INTERVAL: u64 = 256;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SearchLimits {
+    pub max_depth: u8,
+    pub time_limit: Duration,
+}
+
+impl SearchLimits {
+    pub fn new(max_depth: u8, time_limit: Duration) -> Self {
+        Self {
+            max_depth,
+            time_limit,
+        }
+    }
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self::new(6, Duration::from_secs(1))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    // none when the side to move has no legal move
+    pub best_move: Option<(Square, Square)>,
+    // centipawns from the side to move, mates are around `MATE_SCORE`
+    pub score: i32,
+    // deepest iteration that completed
+    pub depth: u8,
+    pub nodes: u64,
+}
+
+impl SearchResult {
+    pub fn is_mate(&self) -> bool {
+        self.score.abs() >= MATE_SCORE - u8::MAX as i32
+    }
+}
+
+struct Searcher {
+    deadline: Instant,
+    nodes: u64,
+    stopped: bool,
+}
+
+impl Searcher {
+    fn is_out_of_time(&mut self) -> bool {
+        if !self.stopped && self.nodes & (TIME_CHECK_INTERVAL - 1) == 0 {
+            self.stopped = Instant::now() >= self.deadline;
+        }
+        self.stopped
+    }
+
+    fn quiescence(&mut self, game: &Game, depth: u8, mut alpha: i32, beta: i32) -> Result<i32> {
+        self.nodes += 1;
+        let color = game.get_current_player_color()?;
+        let stand_pat = evaluate_for(&game.board, color);
+        if depth == 0 || stand_pat >= beta {
+            return Ok(stand_pat);
+        }
+        alpha = alpha.max(stand_pat);
+
+        for (from, to) in get_ordered_moves(game, color) {
+            if game.board.get_piece(to).is_empty() || self.is_out_of_time() {
+                // captures come first, the rest are quiet
+                break;
+            }
+            let score =
+                -self.quiescence(&play(game, color, from, to)?, depth - 1, -beta, -alpha)?;
+            if score >= beta {
+                return Ok(score);
+            }
+            alpha = alpha.max(score);
+        }
+        Ok(alpha)
+    }
+
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: u8,
+        ply: u8,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Result<i32> {
+        if self.is_out_of_time() {
+            return Ok(0);
+        }
+        self.nodes += 1;
+
+        let color = game.get_current_player_color()?;
+        let moves = get_ordered_moves(game, color);
+        if moves.is_empty() {
+            return Ok(if game.in_check(color)? {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            });
+        }
+        if depth == 0 {
+            return self.quiescence(game, QUIESCENCE_DEPTH, alpha, beta);
+        }
+
+        for (from, to) in moves {
+            let score = -self.negamax(
+                &play(game, color, from, to)?,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+            )?;
+            if self.stopped {
+                return Ok(0);
+            }
+            if score >= beta {
+                return Ok(score);
+            }
+            alpha = alpha.max(score);
+        }
+        Ok(alpha)
+    }
+}
+
+fn play(game: &Game, color: Color, from: Square, to: Square) -> Result<Game> {
+    let mut game = game.clone();
+    game.move_piece(color, from, to);
+    game.next_turn()?;
+    Ok(game)
+}
+
+/// Legal moves with captures first, most valuable victim then least valuable
+/// attacker.
+pub fn get_ordered_moves(game: &Game, color: Color) -> Vec<(Square, Square)> {
+    let mut moves = game.get_legal_moves(color);
+    moves.sort_by_key(|&(from, to)| {
+        let victim = get_piece_value(game.board.get_piece(to));
+        if victim == 0 {
+            return 0;
+        }
+        -(victim * 100 - get_piece_value(game.board.get_piece(from)) / 100)
+    });
+    moves
+}
+
+/// Iterative deepening alpha-beta from the side to move, returning the result
+/// of the deepest iteration that finished within the limits. Fails unless the
+/// game is in progress.
+pub fn search(game: &Game, limits: SearchLimits) -> Result<SearchResult> {
+    let color = game.get_current_player_color()?;
+    let mut moves = get_ordered_moves(game, color);
+    let mut result = SearchResult {
+        best_move: moves.first().copied(),
+        score: 0,
+        depth: 0,
+        nodes: 0,
+    };
+    if moves.is_empty() {
+        result.score = if game.in_check(color)? {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return Ok(result);
+    }
+
+    let mut searcher = Searcher {
+        deadline: Instant::now() + limits.time_limit,
+        nodes: 0,
+        stopped: false,
+    };
+    for depth in 1..=limits.max_depth {
+        let mut alpha = -MATE_SCORE - 1;
+        let mut best_move = moves[0];
+        for &(from, to) in &moves {
+            let score = -searcher.negamax(
+                &play(game, color, from, to)?,
+                depth - 1,
+                1,
+                -MATE_SCORE - 1,
+                -alpha,
+            )?;
+            if searcher.stopped {
+                break;
+            }
+            if score > alpha {
+                alpha = score;
+                best_move = (from, to);
+            }
+        }
+        if searcher.stopped {
+            break;
+        }
+
+        result.best_move = Some(best_move);
+        result.score = alpha;
+        result.depth = depth;
+        // the best move of this iteration is searched first in the next one
+        moves.retain(|&mv| mv != best_move);
+        moves.insert(0, best_move);
+        if result.is_mate() {
+            break;
+        }
+    }
+    result.nodes = searcher.nodes;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_game(pieces: &[(Piece, u8, u8)]) -> Game {
+        let mut game = Game::create(GameConfig::default(), 0, Pubkey::default(), 0, 0);
+        game.start_game(0);
+        game.board = Board {
+            board: [[Piece::Empty; 8]; 8],
+        };
+        game.castling_right = CastlingRight {
+            white_kingside: false,
+            white_queenside: false,
+            black_kingside: false,
+            black_queenside: false,
+        };
+        for &(piece, rank, file) in pieces {
+            game.board.set_piece(piece, Square { rank, file });
+        }
+        game
+    }
+
+    #[test]
+    fn finds_back_rank_mate() {
+        let game = create_game(&[
+            (Piece::BlackKing, 0, 7),
+            (Piece::BlackPawn, 1, 6),
+            (Piece::BlackPawn, 1, 7),
+            (Piece::WhiteRook, 7, 0),
+            (Piece::WhiteKing, 7, 6),
+        ]);
+
+        let result = search(&game, SearchLimits::new(3, Duration::from_secs(10))).unwrap();
+        assert_eq!(
+            result.best_move,
+            Some((Square { rank: 7, file: 0 }, Square { rank: 0, file: 0 }))
+        );
+        assert!(result.is_mate());
+        assert!(result.score > 0);
+    }
+
+    #[test]
+    fn takes_hanging_queen() {
+        let game = create_game(&[
+            (Piece::BlackKing, 0, 4),
+            (Piece::BlackQueen, 4, 0),
+            (Piece::WhiteRook, 7, 0),
+            (Piece::WhiteKing, 7, 4),
+        ]);
+
+        let result = search(&game, SearchLimits::new(2, Duration::from_secs(10))).unwrap();
+        assert_eq!(
+            result.best_move,
+            Some((Square { rank: 7, file: 0 }, Square { rank: 4, file: 0 }))
+        );
+    }
+
+    #[test]
+    fn rejects_games_not_in_progress() {
+        let game = Game::create(GameConfig::default(), 0, Pubkey::default(), 0, 0);
+        let error = search(&game, SearchLimits::new(1, Duration::from_secs(10))).unwrap_err();
+        assert_eq!(error, CustomError::InvalidGameState.into());
+    }
+
+    #[test]
+    fn evaluation_is_symmetric_at_start() {
+        let game = Game::create(GameConfig::default(), 0, Pubkey::default(), 0, 0);
+        assert_eq!(evaluate(&game.board), 0);
+    }
+}
@@ -1,7 +1,20 @@
 use anchor_lang::prelude::*;
 
-#[error_code]
-pub enum CustomError {
+// lists every variant once, both for the enum and for `CustomError::ALL`
+macro_rules! custom_errors {
+    ($($(#[$attribute:meta])* $variant:ident,)*) => {
+        #[error_code]
+        pub enum CustomError {
+            $($(#[$attribute])* $variant,)*
+        }
+
+        impl CustomError {
+            pub const ALL: &'static [CustomError] = &[$(CustomError::$variant,)*];
+        }
+    };
+}
+
+custom_errors! {
     #[msg("User Already In Game")]
     UserAlreadyInGame,
     #[msg("Color Not Available")]
@@ -28,4 +41,90 @@ pub enum CustomError {
     AlreadyOfferedDraw,
     #[msg("TimeHasRunOut")]
     TimeHasRunOut,
+    #[msg("Too Many Active Games")]
+    TooManyActiveGames,
+    #[msg("Invalid Max Active Games")]
+    InvalidMaxActiveGames,
+    #[msg("Already In Queue")]
+    AlreadyInQueue,
+    #[msg("Not In Queue")]
+    NotInQueue,
+    #[msg("Queue Full")]
+    QueueFull,
+    #[msg("Rating Out Of Range")]
+    RatingOutOfRange,
+    #[msg("Invalid Rating Range")]
+    InvalidRatingRange,
+    #[msg("Lobby Page Full")]
+    LobbyPageFull,
+    #[msg("Not Invited")]
+    NotInvited,
+    #[msg("Not Game Owner")]
+    NotGameOwner,
+    #[msg("Opponent Already Joined")]
+    OpponentAlreadyJoined,
+    #[msg("Cannot Abort")]
+    CannotAbort,
+    #[msg("Takebacks Not Allowed")]
+    TakebacksNotAllowed,
+    #[msg("Already Requested Takeback")]
+    AlreadyRequestedTakeback,
+    #[msg("No Takeback Requested")]
+    NoTakebackRequested,
+    #[msg("Nothing To Take Back")]
+    NothingToTakeBack,
+    #[msg("Draw Offer Cooldown")]
+    DrawOfferCooldown,
+    #[msg("No Draw Offer")]
+    NoDrawOffer,
+    #[msg("Game Not Finished")]
+    GameNotFinished,
+    #[msg("Already Offered Rematch")]
+    AlreadyOfferedRematch,
+    #[msg("No Rematch Offer")]
+    NoRematchOffer,
+    #[msg("Player Not Inactive")]
+    PlayerNotInactive,
+    #[msg("Too Few Moves To Claim Win")]
+    TooFewMovesToClaimWin,
+    #[msg("Empty Square")]
+    EmptySquare,
+    #[msg("Empty Seat")]
+    EmptySeat,
+    #[msg("Rating Overflow")]
+    RatingOverflow,
+    #[msg("Invalid Move Log")]
+    InvalidMoveLog,
+    #[msg("Invalid Queue Config")]
+    InvalidQueueConfig,
+    #[msg("Invalid Leaderboard Account")]
+    InvalidLeaderboardAccount,
+    #[msg("No King")]
+    NoKing,
+}
+
+impl CustomError {
+    /// Error matching a custom error code returned by a failed instruction.
+    pub fn from_code(code: u32) -> Option<CustomError> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|&error| u32::from(error) == code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, anchor_lang::error::ERROR_CODE_OFFSET};
+
+    #[test]
+    fn every_code_round_trips_through_from_code() {
+        let codes = ERROR_CODE_OFFSET..ERROR_CODE_OFFSET + CustomError::ALL.len() as u32;
+        for (error, code) in CustomError::ALL.iter().copied().zip(codes.clone()) {
+            assert_eq!(u32::from(error), code);
+            assert_eq!(CustomError::from_code(code).map(u32::from), Some(code));
+        }
+        assert!(CustomError::from_code(codes.end).is_none());
+        assert!(CustomError::from_code(ERROR_CODE_OFFSET - 1).is_none());
+    }
 }
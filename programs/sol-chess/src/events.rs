@@ -0,0 +1,192 @@
+use crate::*;
+
+#[event]
+pub struct UserInitialized {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct GameCreated {
+    pub game: Pubkey,
+    pub owner: Pubkey,
+    pub game_config: GameConfig,
+    pub rating_range: Option<RatingRange>,
+    pub is_private: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameJoined {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+}
+
+#[event]
+pub struct GameLeft {
+    pub game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct GameCancelled {
+    pub game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct GameStarted {
+    pub game: Pubkey,
+    pub white: Pubkey,
+    pub black: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MoveMade {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+    pub ply: u16,
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<Piece>,
+    pub capture: Option<Piece>,
+    pub is_check: bool,
+    pub san: String,
+    pub white_timer: u32,
+    pub black_timer: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameAborted {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DrawOffered {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+    pub ply: u16,
+}
+
+#[event]
+pub struct DrawDeclined {
+    pub game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct TakebackRequested {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+}
+
+#[event]
+pub struct TakebackAccepted {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub ply: u16,
+}
+
+#[event]
+pub struct TakebackDeclined {
+    pub game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct GameResigned {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+}
+
+#[event]
+pub struct GameTimedOut {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub color: Color,
+}
+
+#[event]
+pub struct AbandonmentClaimed {
+    pub game: Pubkey,
+    pub user: Pubkey,
+    pub claim_win: bool,
+    pub ply: u16,
+}
+
+#[event]
+pub struct RematchOffered {
+    pub game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct RematchAccepted {
+    pub game: Pubkey,
+    pub rematch_game: Pubkey,
+    pub user: Pubkey,
+}
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct PlayerSettlement {
+    pub user: Pubkey,
+    pub rating_before: u32,
+    pub rating_after: u32,
+    // net change over the game, the escrowed wager included
+    pub balance_delta: i64,
+}
+
+impl PlayerSettlement {
+    pub fn new(user: Pubkey, rating_before: u32, rating_after: u32, balance_delta: i64) -> Self {
+        Self {
+            user,
+            rating_before,
+            rating_after,
+            balance_delta,
+        }
+    }
+}
+
+#[event]
+pub struct GameSettled {
+    pub game: Pubkey,
+    pub game_state: GameState,
+    // none for agreed draws
+    pub termination: Option<Termination>,
+    pub white: PlayerSettlement,
+    pub black: PlayerSettlement,
+    pub timestamp: i64,
+}
+
+impl GameSettled {
+    pub fn new(
+        game: &Account<Game>,
+        termination: Option<Termination>,
+        user: PlayerSettlement,
+        adversary: PlayerSettlement,
+        timestamp: i64,
+    ) -> Result<Self> {
+        let (white, black) = if game.get_player_color(user.user)?.is_white() {
+            (user, adversary)
+        } else {
+            (adversary, user)
+        };
+        Ok(Self {
+            game: game.key(),
+            game_state: game.game_state,
+            termination,
+            white,
+            black,
+            timestamp,
+        })
+    }
+}
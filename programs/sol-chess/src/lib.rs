@@ -5,12 +5,18 @@ use std::mem::size_of;
 declare_id!("9PsU5ntn26Bos8FRtwupQbvoYbchzt8bdQoxLym7AHWB");
 
 pub mod contexts;
+#[cfg(feature = "engine")]
+pub mod engine;
 pub mod error;
+pub mod events;
 pub mod models;
 pub mod states;
 
 pub use contexts::*;
+#[cfg(feature = "engine")]
+pub use engine::*;
 pub use error::*;
+pub use events::*;
 pub use models::*;
 pub use states::*;
 
@@ -22,17 +28,46 @@ pub mod sol_chess {
         ctx.accounts.process()
     }
 
-    pub fn initialize_game(ctx: Context<InitializeGame>, game_config: GameConfig) -> Result<()> {
+    pub fn initialize_game(
+        ctx: Context<InitializeGame>,
+        game_config: GameConfig,
+        rating_range: Option<RatingRange>,
+        invite: Option<Invite>,
+    ) -> Result<()> {
         let game_bump = *ctx.bumps.get("game").unwrap();
-        ctx.accounts.process(game_config, game_bump)
+        let move_log_bump = *ctx.bumps.get("move_log").unwrap();
+        ctx.accounts
+            .process(game_config, rating_range, invite, game_bump, move_log_bump)
     }
 
-    pub fn join_game(ctx: Context<JoinGame>, color: Color) -> Result<()> {
-        ctx.accounts.process(color)
+    pub fn initialize_leaderboard(
+        ctx: Context<InitializeLeaderboard>,
+        category: TimeCategory,
+    ) -> Result<()> {
+        let leaderboard_bump = *ctx.bumps.get("leaderboard").unwrap();
+        ctx.accounts.process(category, leaderboard_bump)
+    }
+
+    pub fn initialize_matchmaking_queue(
+        ctx: Context<InitializeMatchmakingQueue>,
+        game_config: GameConfig,
+    ) -> Result<()> {
+        let queue_bump = *ctx.bumps.get("matchmaking_queue").unwrap();
+        ctx.accounts.process(game_config, queue_bump)
+    }
+
+    pub fn initialize_lobby_page(ctx: Context<InitializeLobbyPage>, page: u32) -> Result<()> {
+        let lobby_page_bump = *ctx.bumps.get("lobby_page").unwrap();
+        ctx.accounts.process(page, lobby_page_bump)
+    }
+
+    pub fn join_game(ctx: Context<JoinGame>, invite_code: Option<String>) -> Result<()> {
+        ctx.accounts.process(invite_code)
     }
 
     pub fn move_piece(ctx: Context<MovePiece>, from: Square, to: Square) -> Result<()> {
-        ctx.accounts.process(from, to)
+        ctx.accounts
+            .process(ctx.remaining_accounts.first(), from, to)
     }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
@@ -49,10 +84,74 @@ pub mod sol_chess {
     }
 
     pub fn resign(ctx: Context<Resign>) -> Result<()> {
-        ctx.accounts.process()
+        ctx.accounts.process(ctx.remaining_accounts.first())
     }
 
     pub fn offer_draw(ctx: Context<OfferDraw>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts.first())
+    }
+
+    pub fn decline_draw(ctx: Context<DeclineDraw>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_max_active_games(
+        ctx: Context<SetMaxActiveGames>,
+        max_active_games: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(max_active_games)
+    }
+
+    pub fn enqueue(ctx: Context<Enqueue>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn dequeue(ctx: Context<Dequeue>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn match_players(ctx: Context<MatchPlayers>) -> Result<()> {
+        let game_bump = *ctx.bumps.get("game").unwrap();
+        let move_log_bump = *ctx.bumps.get("move_log").unwrap();
+        ctx.accounts.process(game_bump, move_log_bump)
+    }
+
+    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn abort(ctx: Context<Abort>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn check_timer(ctx: Context<CheckTimer>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts.first())
+    }
+
+    pub fn claim_abandonment(ctx: Context<ClaimAbandonment>, claim_win: bool) -> Result<()> {
+        ctx.accounts
+            .process(ctx.remaining_accounts.first(), claim_win)
+    }
+
+    pub fn offer_rematch(ctx: Context<OfferRematch>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn accept_rematch(ctx: Context<AcceptRematch>) -> Result<()> {
+        let rematch_game_bump = *ctx.bumps.get("rematch_game").unwrap();
+        let move_log_bump = *ctx.bumps.get("move_log").unwrap();
+        ctx.accounts.process(rematch_game_bump, move_log_bump)
+    }
+
+    pub fn request_takeback(ctx: Context<RequestTakeback>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn accept_takeback(ctx: Context<AcceptTakeback>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn decline_takeback(ctx: Context<DeclineTakeback>) -> Result<()> {
         ctx.accounts.process()
     }
 }
@@ -208,7 +208,7 @@ impl Board {
         // pawn
         for pawn_attack_square in pawn_attack_squares {
             let piece = self.get_piece(pawn_attack_square);
-            if piece.is_pawn() && piece.get_color().is_opposite(color) {
+            if piece.is_pawn() && piece.is_color(color.get_opposite()) {
                 return true;
             }
         }
@@ -216,14 +216,14 @@ impl Board {
         // king
         for adjacent_square in adjacent_squares {
             let piece = self.get_piece(adjacent_square);
-            if piece.is_king() && piece.get_color().is_opposite(color) {
+            if piece.is_king() && piece.is_color(color.get_opposite()) {
                 return true;
             }
         }
 
         // bishop / queen
         for diagonal_piece in diagonal_pieces {
-            if diagonal_piece.0.get_color().is_opposite(color)
+            if diagonal_piece.0.is_color(color.get_opposite())
                 && (diagonal_piece.0.is_bishop() || diagonal_piece.0.is_queen())
             {
                 return true;
@@ -232,7 +232,7 @@ impl Board {
 
         // rook / queen
         for parallel_piece in parallel_pieces {
-            if parallel_piece.0.get_color().is_opposite(color)
+            if parallel_piece.0.is_color(color.get_opposite())
                 && (parallel_piece.0.is_rook() || parallel_piece.0.is_queen())
             {
                 return true;
@@ -241,7 +241,7 @@ impl Board {
 
         // knight
         for knight_jump_piece in knight_jump_pieces {
-            if knight_jump_piece.0.get_color().is_opposite(color) && knight_jump_piece.0.is_knight()
+            if knight_jump_piece.0.is_color(color.get_opposite()) && knight_jump_piece.0.is_knight()
             {
                 return true;
             }
@@ -303,13 +303,29 @@ impl Board {
         }
     }
 
+    pub fn undo_kingside_castle_rook(&mut self, color: Color) {
+        if color.is_white() {
+            self.move_piece(Square { rank: 7, file: 5 }, Square { rank: 7, file: 7 })
+        } else {
+            self.move_piece(Square { rank: 0, file: 5 }, Square { rank: 0, file: 7 })
+        }
+    }
+
+    pub fn undo_queenside_castle_rook(&mut self, color: Color) {
+        if color.is_white() {
+            self.move_piece(Square { rank: 7, file: 3 }, Square { rank: 7, file: 0 })
+        } else {
+            self.move_piece(Square { rank: 0, file: 3 }, Square { rank: 0, file: 0 })
+        }
+    }
+
     pub fn get_color_pieces(&self, color: Color) -> Vec<(Piece, Square)> {
         let mut pieces = vec![];
         for rank in 0..8 {
             for file in 0..8 {
                 let square = Square { rank, file };
                 let piece = self.get_piece(square);
-                if piece.is_not_empty() && piece.get_color().is_color(color) {
+                if piece.is_color(color) {
                     pieces.push((piece, square));
                 }
             }
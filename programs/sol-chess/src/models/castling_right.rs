@@ -1,6 +1,6 @@
 use crate::*;
 
-#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
 pub struct CastlingRight {
     pub white_kingside: bool,
     pub white_queenside: bool,
@@ -0,0 +1,25 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Default)]
+pub enum ColorPreference {
+    White,
+    Black,
+    /// Decided when the opponent joins from the slot hash, which the joiner
+    /// can bias by retrying until they get the color they want.
+    #[default]
+    Random,
+}
+
+impl ColorPreference {
+    pub fn is_random(&self) -> bool {
+        self == &ColorPreference::Random
+    }
+
+    // random games seat the creator as white until the opponent joins
+    pub fn get_creator_color(&self) -> Color {
+        match self {
+            ColorPreference::Black => Color::Black,
+            _ => Color::White,
+        }
+    }
+}
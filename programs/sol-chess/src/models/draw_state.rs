@@ -1,73 +1,191 @@
 use crate::*;
 
+// plies a player has to wait between two of their own draw offers
+pub const DRAW_OFFER_COOLDOWN: u16 = 2;
+
 #[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
-pub enum DrawState {
-    Neither,
-    White,
-    Black,
-    Draw,
+pub struct DrawOffer {
+    pub color: Color,
+    // ply the offer was made on
+    pub ply: u16,
+}
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Default)]
+pub struct DrawState {
+    pub offer: Option<DrawOffer>,
+    pub white_last_offer: Option<u16>,
+    pub black_last_offer: Option<u16>,
+    pub accepted: bool,
 }
 
 impl DrawState {
+    pub fn get_offer(&self) -> Option<DrawOffer> {
+        self.offer
+    }
+
     pub fn color_offered(&self, color: Color) -> bool {
+        self.offer.map(|offer| offer.color) == Some(color)
+    }
+
+    pub fn get_last_offer(&self, color: Color) -> Option<u16> {
         if color.is_white() {
-            self == &DrawState::White
+            self.white_last_offer
         } else {
-            self == &DrawState::Black
+            self.black_last_offer
         }
     }
 
-    pub fn set_color(&mut self, color: Color) {
+    fn set_last_offer(&mut self, color: Color, ply: u16) {
         if color.is_white() {
-            *self = DrawState::White;
+            self.white_last_offer = Some(ply);
         } else {
-            *self = DrawState::Black;
+            self.black_last_offer = Some(ply);
         }
     }
 
-    pub fn white_offered(&self) -> bool {
-        self == &DrawState::White
+    pub fn can_offer(&self, color: Color, ply: u16) -> bool {
+        if self.color_offered(color) {
+            return false;
+        }
+        match self.get_last_offer(color) {
+            Some(last_offer) => ply >= last_offer + DRAW_OFFER_COOLDOWN,
+            None => true,
+        }
     }
 
-    pub fn black_offered(&self) -> bool {
-        self == &DrawState::Black
+    pub fn is_draw(&self) -> bool {
+        self.accepted
     }
 
-    pub fn is_draw_with(&self, color: Color) -> bool {
-        color.is_white() && self.black_offered() || color.is_black() && self.white_offered()
+    pub fn offer(&mut self, color: Color, ply: u16) {
+        self.offer = Some(DrawOffer { color, ply });
+        self.set_last_offer(color, ply);
     }
 
-    pub fn is_draw(&self) -> bool {
-        self == &DrawState::Draw
+    pub fn accept(&mut self) {
+        self.offer = None;
+        self.accepted = true;
+    }
+
+    pub fn decline(&mut self) {
+        self.offer = None;
     }
 
-    pub fn is_neither(&self) -> bool {
-        self == &DrawState::Neither
+    // offering while the opponent's offer is pending accepts it
+    pub fn update_state(&mut self, color: Color, ply: u16) {
+        if self.color_offered(color.get_opposite()) {
+            self.accept();
+        } else {
+            self.offer(color, ply);
+        }
     }
 
+    // an offer stands until the player it was made to moves
+    pub fn on_move(&mut self, color: Color) {
+        if self.color_offered(color.get_opposite()) {
+            self.offer = None;
+        }
+    }
+
+    // cooldowns survive a reset so offers cannot be spammed through takebacks
     pub fn reset(&mut self) {
-        *self = DrawState::Neither;
+        self.offer = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offer_records_offering_color_and_ply() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::Black, 3);
+
+        assert!(draw_state.color_offered(Color::Black));
+        assert!(!draw_state.color_offered(Color::White));
+        assert_eq!(
+            draw_state.get_offer(),
+            Some(DrawOffer {
+                color: Color::Black,
+                ply: 3
+            })
+        );
+        assert!(!draw_state.is_draw());
     }
 
-    pub fn set_white(&mut self) {
-        *self = DrawState::White;
+    #[test]
+    fn counter_offer_accepts() {
+        let mut draw_state = DrawState::default();
+        draw_state.update_state(Color::White, 0);
+        draw_state.update_state(Color::Black, 0);
+
+        assert!(draw_state.is_draw());
+        assert_eq!(draw_state.get_offer(), None);
     }
 
-    pub fn set_black(&mut self) {
-        *self = DrawState::White;
+    #[test]
+    fn decline_clears_offer() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::White, 4);
+        draw_state.decline();
+
+        assert_eq!(draw_state.get_offer(), None);
+        assert!(!draw_state.is_draw());
+
+        // a declined offer cannot be accepted anymore
+        draw_state.update_state(Color::Black, 4);
+        assert!(!draw_state.is_draw());
+        assert!(draw_state.color_offered(Color::Black));
     }
 
-    pub fn set_draw(&mut self) {
-        *self = DrawState::Draw;
+    #[test]
+    fn offer_survives_offering_players_move() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::White, 4);
+        draw_state.on_move(Color::White);
+
+        assert!(draw_state.color_offered(Color::White));
     }
 
-    pub fn update_state(&mut self, color: Color) {
-        if color.is_white() && self.black_offered() {
-            self.set_draw();
-        } else if color.is_black() && self.white_offered() {
-            self.set_draw();
-        } else if self.is_neither() {
-            self.set_color(color);
-        }
+    #[test]
+    fn offer_expires_when_opponent_moves() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::White, 4);
+        draw_state.on_move(Color::Black);
+
+        assert_eq!(draw_state.get_offer(), None);
+        assert!(!draw_state.is_draw());
+    }
+
+    #[test]
+    fn pending_offer_cannot_be_repeated() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::White, 4);
+
+        assert!(!draw_state.can_offer(Color::White, 10));
+        assert!(draw_state.can_offer(Color::Black, 4));
+    }
+
+    #[test]
+    fn offers_are_limited_per_move() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::White, 4);
+        draw_state.decline();
+
+        assert!(!draw_state.can_offer(Color::White, 4));
+        assert!(!draw_state.can_offer(Color::White, 5));
+        assert!(draw_state.can_offer(Color::White, 6));
+    }
+
+    #[test]
+    fn reset_keeps_cooldown() {
+        let mut draw_state = DrawState::default();
+        draw_state.offer(Color::Black, 5);
+        draw_state.reset();
+
+        assert_eq!(draw_state.get_offer(), None);
+        assert!(!draw_state.can_offer(Color::Black, 5));
+        assert!(draw_state.can_offer(Color::Black, 7));
     }
 }
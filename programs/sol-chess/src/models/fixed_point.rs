@@ -0,0 +1,166 @@
+use {
+    crate::*,
+    std::ops::{Add, Neg, Sub},
+};
+
+pub const FIXED_POINT_SCALE: i128 = 1_000_000_000;
+
+const LN_2: FixedPoint = FixedPoint(693_147_181);
+const EXP_MIN: FixedPoint = FixedPoint(-40 * FIXED_POINT_SCALE);
+const EXP_MAX: FixedPoint = FixedPoint(40 * FIXED_POINT_SCALE);
+
+/// Signed fixed-point number with 9 decimals, used for on-chain rating math
+/// where floating point is not deterministic enough to rely on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FixedPoint(pub i128);
+
+impl FixedPoint {
+    pub const ZERO: FixedPoint = FixedPoint(0);
+    pub const HALF: FixedPoint = FixedPoint(FIXED_POINT_SCALE / 2);
+    pub const ONE: FixedPoint = FixedPoint(FIXED_POINT_SCALE);
+
+    pub fn from_int(value: i64) -> Self {
+        FixedPoint(value as i128 * FIXED_POINT_SCALE)
+    }
+
+    pub fn from_ratio(numerator: i64, denominator: i64) -> Self {
+        FixedPoint(numerator as i128 * FIXED_POINT_SCALE / denominator as i128)
+    }
+
+    pub fn from_raw(raw: i64) -> Self {
+        FixedPoint(raw as i128)
+    }
+
+    pub fn to_raw(&self) -> i64 {
+        self.0 as i64
+    }
+
+    pub fn round(&self) -> i64 {
+        ((self.0 + FIXED_POINT_SCALE / 2).div_euclid(FIXED_POINT_SCALE)) as i64
+    }
+
+    pub fn abs(&self) -> Self {
+        FixedPoint(self.0.abs())
+    }
+
+    /// Product, failing instead of overflowing on large operands.
+    pub fn checked_mul(&self, rhs: FixedPoint) -> Result<Self> {
+        self.0
+            .checked_mul(rhs.0)
+            .map(|product| FixedPoint(product / FIXED_POINT_SCALE))
+            .ok_or_else(|| error!(CustomError::RatingOverflow))
+    }
+
+    /// Quotient, failing instead of overflowing or dividing by zero.
+    pub fn checked_div(&self, rhs: FixedPoint) -> Result<Self> {
+        self.0
+            .checked_mul(FIXED_POINT_SCALE)
+            .and_then(|numerator| numerator.checked_div(rhs.0))
+            .map(FixedPoint)
+            .ok_or_else(|| error!(CustomError::RatingOverflow))
+    }
+
+    pub fn square(&self) -> Result<Self> {
+        self.checked_mul(*self)
+    }
+
+    pub fn sqrt(&self) -> Result<Self> {
+        if self.0 <= 0 {
+            return Ok(FixedPoint::ZERO);
+        }
+
+        // integer square root of value * scale, so the result keeps the scale
+        let target = (self.0 as u128)
+            .checked_mul(FIXED_POINT_SCALE as u128)
+            .ok_or_else(|| error!(CustomError::RatingOverflow))?;
+        let mut root = target;
+        let mut next = (root + target / root) / 2;
+        while next < root {
+            root = next;
+            next = (root + target / root) / 2;
+        }
+        Ok(FixedPoint(root as i128))
+    }
+
+    pub fn exp(&self) -> Result<Self> {
+        let x = (*self).clamp(EXP_MIN, EXP_MAX);
+
+        // e^x = 2^k * e^r with |r| <= ln(2) / 2
+        let k = (x.0 + LN_2.0 / 2).div_euclid(LN_2.0);
+        let r = x - FixedPoint(k * LN_2.0);
+
+        let mut sum = FixedPoint::ONE;
+        let mut term = FixedPoint::ONE;
+        for n in 1..=20 {
+            term = term.checked_mul(r)?.checked_div(FixedPoint::from_int(n))?;
+            if term == FixedPoint::ZERO {
+                break;
+            }
+            sum = sum + term;
+        }
+
+        if k >= 0 {
+            Ok(FixedPoint(sum.0 << k))
+        } else {
+            Ok(FixedPoint(sum.0 >> -k))
+        }
+    }
+
+    pub fn ln(&self) -> Result<Self> {
+        if self.0 <= 0 {
+            return Ok(EXP_MIN);
+        }
+
+        // x = 2^k * m with 1 <= m < 2
+        let mut m = *self;
+        let mut k = 0;
+        while m.0 >= 2 * FIXED_POINT_SCALE {
+            m = FixedPoint(m.0 >> 1);
+            k += 1;
+        }
+        while m.0 < FIXED_POINT_SCALE {
+            m = FixedPoint(m.0 << 1);
+            k -= 1;
+        }
+
+        // ln(m) = 2 * atanh((m - 1) / (m + 1))
+        let z = (m - FixedPoint::ONE).checked_div(m + FixedPoint::ONE)?;
+        let z_squared = z.square()?;
+        let mut sum = FixedPoint::ZERO;
+        let mut power = z;
+        for n in (1..40).step_by(2) {
+            let term = power.checked_div(FixedPoint::from_int(n))?;
+            if term == FixedPoint::ZERO {
+                break;
+            }
+            sum = sum + term;
+            power = power.checked_mul(z_squared)?;
+        }
+
+        Ok(FixedPoint(k * LN_2.0) + sum + sum)
+    }
+}
+
+impl Add for FixedPoint {
+    type Output = FixedPoint;
+
+    fn add(self, rhs: FixedPoint) -> FixedPoint {
+        FixedPoint(self.0 + rhs.0)
+    }
+}
+
+impl Sub for FixedPoint {
+    type Output = FixedPoint;
+
+    fn sub(self, rhs: FixedPoint) -> FixedPoint {
+        FixedPoint(self.0 - rhs.0)
+    }
+}
+
+impl Neg for FixedPoint {
+    type Output = FixedPoint;
+
+    fn neg(self) -> FixedPoint {
+        FixedPoint(-self.0)
+    }
+}
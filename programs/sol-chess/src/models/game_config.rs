@@ -1,11 +1,19 @@
 use crate::*;
 
-#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize)]
+pub const DEFAULT_INACTIVITY_THRESHOLD: u32 = 60 * 60 * 24;
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
 pub struct GameConfig {
     pub timer: u32,
     pub increment: u32,
     pub is_rated: bool,
     pub wager: Option<u64>,
+    pub color: ColorPreference,
+    // only consulted for rated games, unrated games always allow takebacks
+    pub allow_takebacks: bool,
+    // seconds without a move before the waiting player may claim abandonment,
+    // independent of the clock
+    pub inactivity_threshold: Option<u32>,
 }
 
 impl GameConfig {
@@ -21,14 +29,35 @@ impl GameConfig {
         self.wager.unwrap()
     }
 
+    pub fn get_wager_or_zero(&self) -> u64 {
+        self.wager.unwrap_or(0)
+    }
+
     pub fn has_wager(&self) -> bool {
         self.wager.is_some()
     }
 
+    pub fn get_color_preference(&self) -> ColorPreference {
+        self.color
+    }
+
+    pub fn allows_takebacks(&self) -> bool {
+        !self.is_rated || self.allow_takebacks
+    }
+
+    pub fn get_inactivity_threshold(&self) -> u32 {
+        self.inactivity_threshold
+            .unwrap_or(DEFAULT_INACTIVITY_THRESHOLD)
+    }
+
     pub fn is_rated(&self) -> bool {
         self.is_rated
     }
 
+    pub fn get_time_category(&self) -> TimeCategory {
+        TimeCategory::from_time_control(self.get_timer(), self.get_increment())
+    }
+
     pub fn get_time_control(&self) -> TimeControl {
         TimeControl::new(self.get_timer(), self.get_increment())
     }
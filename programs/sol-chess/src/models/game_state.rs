@@ -8,14 +8,15 @@ pub enum GameState {
     WhiteWon,
     BlackWon,
     Draw,
+    Aborted,
 }
 
 impl GameState {
-    pub fn get_current_player_turn(&self) -> Color {
+    pub fn get_current_player_turn(&self) -> Result<Color> {
         match self {
-            GameState::White => Color::White,
-            GameState::Black => Color::Black,
-            _ => panic!("Invalid Game State"),
+            GameState::White => Ok(Color::White),
+            GameState::Black => Ok(Color::Black),
+            _ => err!(CustomError::InvalidGameState),
         }
     }
 
@@ -27,11 +28,11 @@ impl GameState {
         self == &GameState::Black
     }
 
-    pub fn next_turn(&self) -> Self {
+    pub fn next_turn(&self) -> Result<Self> {
         match self {
-            GameState::White => GameState::Black,
-            GameState::Black => GameState::White,
-            _ => panic!("Invalid Game State"),
+            GameState::White => Ok(GameState::Black),
+            GameState::Black => Ok(GameState::White),
+            _ => err!(CustomError::InvalidGameState),
         }
     }
 
@@ -47,6 +48,10 @@ impl GameState {
         *self = GameState::Draw;
     }
 
+    pub fn set_aborted(&mut self) {
+        *self = GameState::Aborted;
+    }
+
     pub fn is_still_going(&self) -> bool {
         self == &Self::White || self == &Self::Black
     }
@@ -56,7 +61,10 @@ impl GameState {
     }
 
     pub fn is_finished(&self) -> bool {
-        self == &Self::WhiteWon || self == &Self::BlackWon || self == &Self::Draw
+        self == &Self::WhiteWon
+            || self == &Self::BlackWon
+            || self == &Self::Draw
+            || self == &Self::Aborted
     }
 
     pub fn start_game(&mut self) {
@@ -0,0 +1,26 @@
+use crate::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Restricts who may take the seat left open by the creator, either a given
+/// user account or anyone presenting the code whose hash is stored.
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum Invite {
+    User(Pubkey),
+    Code([u8; 32]),
+}
+
+impl Invite {
+    pub fn hash_code(code: &str) -> [u8; 32] {
+        hash(code.as_bytes()).to_bytes()
+    }
+
+    pub fn accepts(&self, user: Pubkey, code: Option<&str>) -> bool {
+        match self {
+            Invite::User(invited_user) => invited_user == &user,
+            Invite::Code(code_hash) => match code {
+                Some(code) => &Self::hash_code(code) == code_hash,
+                None => false,
+            },
+        }
+    }
+}
@@ -1,19 +1,43 @@
 pub mod board;
 pub mod castling_right;
 pub mod color;
+pub mod color_preference;
 pub mod draw_state;
+pub mod fixed_point;
 pub mod game_config;
 pub mod game_state;
+pub mod invite;
+pub mod move_undo;
+pub mod outcome;
 pub mod piece;
+pub mod rating;
+pub mod rating_range;
+pub mod rematch_state;
 pub mod square;
+pub mod takeback_state;
+pub mod termination;
+pub mod time_category;
 pub mod time_control;
+pub mod user_stats;
 
 pub use board::*;
 pub use castling_right::*;
 pub use color::*;
+pub use color_preference::*;
 pub use draw_state::*;
+pub use fixed_point::*;
 pub use game_config::*;
 pub use game_state::*;
+pub use invite::*;
+pub use move_undo::*;
+pub use outcome::*;
 pub use piece::*;
+pub use rating::*;
+pub use rating_range::*;
+pub use rematch_state::*;
 pub use square::*;
+pub use takeback_state::*;
+pub use termination::*;
+pub use time_category::*;
 pub use time_control::*;
+pub use user_stats::*;
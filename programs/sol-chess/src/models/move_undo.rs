@@ -0,0 +1,32 @@
+use crate::*;
+
+pub const UNDO_HISTORY_SIZE: usize = 2;
+
+/// Everything a move overwrites, so that it can be taken back precisely.
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct MoveUndo {
+    pub from: Square,
+    pub to: Square,
+    // piece on `from` before the move, a pawn for promotions
+    pub piece: Piece,
+    pub captured: Piece,
+    // differs from `to` for en passant captures
+    pub captured_square: Square,
+    pub castling_right: CastlingRight,
+    pub enpassant: Option<Square>,
+    pub time_control: TimeControl,
+}
+
+impl MoveUndo {
+    pub fn is_kingside_castle(&self, color: Color) -> bool {
+        self.piece.is_king()
+            && self.from.is_king_square(color)
+            && self.to.is_kingside_castle_square(color)
+    }
+
+    pub fn is_queenside_castle(&self, color: Color) -> bool {
+        self.piece.is_king()
+            && self.from.is_king_square(color)
+            && self.to.is_queenside_castle_square(color)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::*;
+
+/// How a finished game ended, `Draw` being an agreed draw.
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum Outcome {
+    Win(Color, Termination),
+    Draw,
+}
+
+impl Outcome {
+    pub fn get_termination(&self) -> Option<Termination> {
+        match self {
+            Outcome::Win(_, termination) => Some(*termination),
+            Outcome::Draw => None,
+        }
+    }
+
+    pub fn is_winner(&self, color: Color) -> bool {
+        matches!(self, Outcome::Win(winner, _) if winner == &color)
+    }
+
+    pub fn is_loser(&self, color: Color) -> bool {
+        matches!(self, Outcome::Win(winner, _) if winner != &color)
+    }
+
+    // game score of the player of `color` for the rating update
+    pub fn get_score(&self, color: Color) -> FixedPoint {
+        if self.is_winner(color) {
+            FixedPoint::ONE
+        } else if self.is_loser(color) {
+            FixedPoint::ZERO
+        } else {
+            FixedPoint::HALF
+        }
+    }
+
+    // net change of the balance of the player of `color`, the escrowed wager included
+    pub fn get_balance_delta(&self, color: Color, wager: u64) -> i64 {
+        if self.is_winner(color) {
+            wager as i64
+        } else if self.is_loser(color) {
+            -(wager as i64)
+        } else {
+            0
+        }
+    }
+}
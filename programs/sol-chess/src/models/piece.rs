@@ -32,7 +32,7 @@ impl Piece {
             | Piece::BlackBishop
             | Piece::BlackQueen
             | Piece::BlackKing => true,
-            Piece::Empty => panic!("Empty has no Color"),
+            Piece::Empty => false,
         }
     }
 
@@ -50,25 +50,34 @@ impl Piece {
             | Piece::BlackBishop
             | Piece::BlackQueen
             | Piece::BlackKing => false,
-            Piece::Empty => panic!("Empty has no Color"),
+            Piece::Empty => false,
         }
     }
 
-    pub fn get_color(&self) -> Color {
+    pub fn get_color(&self) -> Result<Color> {
         match self {
             Piece::WhitePawn
             | Piece::WhiteRook
             | Piece::WhiteKnight
             | Piece::WhiteBishop
             | Piece::WhiteQueen
-            | Piece::WhiteKing => Color::White,
+            | Piece::WhiteKing => Ok(Color::White),
             Piece::BlackPawn
             | Piece::BlackRook
             | Piece::BlackKnight
             | Piece::BlackBishop
             | Piece::BlackQueen
-            | Piece::BlackKing => Color::Black,
-            Piece::Empty => panic!("Empty has no Color"),
+            | Piece::BlackKing => Ok(Color::Black),
+            Piece::Empty => err!(CustomError::EmptySquare),
+        }
+    }
+
+    // never true of an empty square
+    pub fn is_color(&self, color: Color) -> bool {
+        if color.is_white() {
+            self.is_white()
+        } else {
+            self.is_black()
         }
     }
 
@@ -127,6 +136,23 @@ impl Piece {
         }
         return false;
     }
+
+    // piece letter used in algebraic notation, pawns have none
+    pub fn get_san_letter(&self) -> Option<char> {
+        if self.is_king() {
+            Some('K')
+        } else if self.is_queen() {
+            Some('Q')
+        } else if self.is_rook() {
+            Some('R')
+        } else if self.is_bishop() {
+            Some('B')
+        } else if self.is_knight() {
+            Some('N')
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for Piece {
@@ -0,0 +1,345 @@
+use crate::*;
+
+pub const DEFAULT_RATING: i64 = 1500;
+pub const DEFAULT_DEVIATION: i64 = 350;
+pub const MIN_DEVIATION: i64 = 30;
+pub const RATING_PERIOD: i64 = 60 * 60 * 24;
+
+// glicko-2 system constant, constrains the change in volatility
+const TAU: FixedPoint = FixedPoint::HALF;
+const CONVERGENCE_TOLERANCE: FixedPoint = FixedPoint(1_000);
+// updates converge within 5 evaluations of the volatility function across
+// ratings, deviations and volatilities in play, the bound only caps the
+// compute spent on a settlement
+const MAX_ITERATIONS: usize = 8;
+
+/// Glicko-2 rating stored in the glicko-1 scale, `rating`, `deviation` and
+/// `volatility` are raw `FixedPoint`s.
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct Rating {
+    pub rating: i64,
+    pub deviation: i64,
+    pub volatility: i64,
+    pub last_game: i64,
+    pub games: u32,
+}
+
+impl Rating {
+    fn glicko_scale() -> FixedPoint {
+        FixedPoint::from_ratio(1_737_178, 10_000)
+    }
+
+    fn pi_squared() -> FixedPoint {
+        FixedPoint(9_869_604_401)
+    }
+
+    pub fn get_rating(&self) -> FixedPoint {
+        FixedPoint::from_raw(self.rating)
+    }
+
+    pub fn get_deviation(&self) -> FixedPoint {
+        FixedPoint::from_raw(self.deviation)
+    }
+
+    pub fn get_volatility(&self) -> FixedPoint {
+        FixedPoint::from_raw(self.volatility)
+    }
+
+    /// Elo-compatible view of the rating, used for display and pairing.
+    pub fn get_elo(&self) -> u32 {
+        self.get_rating().round().max(0) as u32
+    }
+
+    pub fn has_played(&self) -> bool {
+        self.last_game != 0
+    }
+
+    /// Deviation grown by the number of rating periods spent without playing.
+    pub fn get_decayed_deviation(&self, current_timestamp: i64) -> Result<FixedPoint> {
+        let deviation = self.get_deviation();
+        if !self.has_played() || current_timestamp <= self.last_game {
+            return Ok(deviation);
+        }
+
+        let periods = FixedPoint::from_ratio(current_timestamp - self.last_game, RATING_PERIOD);
+        let growth = periods
+            .checked_mul(self.get_volatility().square()?)?
+            .checked_mul(Self::glicko_scale().square()?)?;
+        let decayed_deviation = (deviation.square()? + growth).sqrt()?;
+        Ok(decayed_deviation.min(FixedPoint::from_int(DEFAULT_DEVIATION)))
+    }
+
+    fn get_g(phi: FixedPoint) -> Result<FixedPoint> {
+        let three = FixedPoint::from_int(3);
+        let spread = three
+            .checked_mul(phi.square()?)?
+            .checked_div(Self::pi_squared())?;
+        FixedPoint::ONE.checked_div((FixedPoint::ONE + spread).sqrt()?)
+    }
+
+    fn get_expected_score(
+        mu: FixedPoint,
+        adversary_mu: FixedPoint,
+        g: FixedPoint,
+    ) -> Result<FixedPoint> {
+        let exponent = -g.checked_mul(mu - adversary_mu)?;
+        let expected_score = FixedPoint::ONE.checked_div(FixedPoint::ONE + exponent.exp()?)?;
+        // keep the variance finite for extreme rating gaps
+        Ok(expected_score
+            .max(FixedPoint(1_000))
+            .min(FixedPoint::ONE - FixedPoint(1_000)))
+    }
+
+    fn get_new_volatility(
+        &self,
+        phi: FixedPoint,
+        variance: FixedPoint,
+        delta: FixedPoint,
+    ) -> Result<FixedPoint> {
+        let a = self.get_volatility().square()?.ln()?;
+        let phi_squared = phi.square()?;
+        let delta_squared = delta.square()?;
+        let tau_squared = TAU.square()?;
+
+        let f = |x: FixedPoint| -> Result<FixedPoint> {
+            let exp_x = x.exp()?;
+            let denominator = phi_squared + variance + exp_x;
+            let change = exp_x
+                .checked_mul(delta_squared - phi_squared - variance - exp_x)?
+                .checked_div(FixedPoint::from_int(2).checked_mul(denominator.square()?)?)?;
+            Ok(change - (x - a).checked_div(tau_squared)?)
+        };
+
+        // illinois algorithm
+        let mut lower = a;
+        let mut upper = if delta_squared > phi_squared + variance {
+            (delta_squared - phi_squared - variance).ln()?
+        } else {
+            let mut k = 1;
+            while f(a - FixedPoint::from_int(k).checked_mul(TAU)?)? < FixedPoint::ZERO
+                && (k as usize) < MAX_ITERATIONS
+            {
+                k += 1;
+            }
+            a - FixedPoint::from_int(k).checked_mul(TAU)?
+        };
+
+        let mut f_lower = f(lower)?;
+        let mut f_upper = f(upper)?;
+        for _ in 0..MAX_ITERATIONS {
+            if (upper - lower).abs() <= CONVERGENCE_TOLERANCE || f_upper == f_lower {
+                break;
+            }
+
+            let candidate = lower
+                + (lower - upper)
+                    .checked_mul(f_lower)?
+                    .checked_div(f_upper - f_lower)?;
+            // the bracket cannot shrink past the fixed-point resolution
+            if candidate == lower || candidate == upper {
+                break;
+            }
+            let f_candidate = f(candidate)?;
+            if f_candidate.0.signum() * f_upper.0.signum() <= 0 {
+                lower = upper;
+                f_lower = f_upper;
+            } else {
+                f_lower = FixedPoint(f_lower.0 / 2);
+            }
+            upper = candidate;
+            f_upper = f_candidate;
+        }
+
+        lower.checked_div(FixedPoint::from_int(2))?.exp()
+    }
+
+    /// Rating after a single game against `adversary`, treating each game as
+    /// its own rating period. `score` is 1 for a win, 0.5 for a draw and 0
+    /// for a loss.
+    pub fn get_new_rating(
+        &self,
+        adversary: Rating,
+        score: FixedPoint,
+        current_timestamp: i64,
+    ) -> Result<Rating> {
+        self.get_period_rating(&[(adversary, score)], current_timestamp)
+    }
+
+    /// Rating after a rating period made of every `(adversary, score)` game.
+    pub fn get_period_rating(
+        &self,
+        results: &[(Rating, FixedPoint)],
+        current_timestamp: i64,
+    ) -> Result<Rating> {
+        let scale = Self::glicko_scale();
+        let default_rating = FixedPoint::from_int(DEFAULT_RATING);
+
+        let mu = (self.get_rating() - default_rating).checked_div(scale)?;
+        let phi = self
+            .get_decayed_deviation(current_timestamp)?
+            .checked_div(scale)?;
+
+        let mut information = FixedPoint::ZERO;
+        let mut improvement = FixedPoint::ZERO;
+        for (adversary, score) in results {
+            let adversary_mu = (adversary.get_rating() - default_rating).checked_div(scale)?;
+            let adversary_phi = adversary
+                .get_decayed_deviation(current_timestamp)?
+                .checked_div(scale)?;
+
+            let g = Self::get_g(adversary_phi)?;
+            let expected_score = Self::get_expected_score(mu, adversary_mu, g)?;
+            information = information
+                + g.square()?
+                    .checked_mul(expected_score)?
+                    .checked_mul(FixedPoint::ONE - expected_score)?;
+            improvement = improvement + g.checked_mul(*score - expected_score)?;
+        }
+        let variance = FixedPoint::ONE.checked_div(information)?;
+        let delta = variance.checked_mul(improvement)?;
+
+        let new_volatility = self.get_new_volatility(phi, variance, delta)?;
+        let pre_period_phi = (phi.square()? + new_volatility.square()?).sqrt()?;
+        let precision = FixedPoint::ONE.checked_div(pre_period_phi.square()?)?
+            + FixedPoint::ONE.checked_div(variance)?;
+        let new_phi = FixedPoint::ONE.checked_div(precision.sqrt()?)?;
+        let new_mu = mu + new_phi.square()?.checked_mul(improvement)?;
+
+        let new_deviation = new_phi
+            .checked_mul(scale)?
+            .max(FixedPoint::from_int(MIN_DEVIATION))
+            .min(FixedPoint::from_int(DEFAULT_DEVIATION));
+        let new_rating = new_mu.checked_mul(scale)? + default_rating;
+
+        Ok(Rating {
+            rating: to_raw(new_rating)?,
+            deviation: new_deviation.to_raw(),
+            volatility: to_raw(new_volatility)?,
+            last_game: current_timestamp,
+            games: self.games + results.len() as u32,
+        })
+    }
+}
+
+// ratings are stored as raw i64s, which a runaway update must not wrap
+fn to_raw(value: FixedPoint) -> Result<i64> {
+    i64::try_from(value.0).map_err(|_| error!(CustomError::RatingOverflow))
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            rating: FixedPoint::from_int(DEFAULT_RATING).to_raw(),
+            deviation: FixedPoint::from_int(DEFAULT_DEVIATION).to_raw(),
+            volatility: FixedPoint::from_ratio(6, 100).to_raw(),
+            last_game: 0,
+            games: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(rating: i64, deviation: i64) -> Rating {
+        Rating {
+            rating: FixedPoint::from_int(rating).to_raw(),
+            deviation: FixedPoint::from_int(deviation).to_raw(),
+            ..Rating::default()
+        }
+    }
+
+    fn assert_close(value: FixedPoint, expected: f64, tolerance: f64) {
+        let value = value.0 as f64 / FIXED_POINT_SCALE as f64;
+        assert!(
+            (value - expected).abs() <= tolerance,
+            "{} is not within {} of {}",
+            value,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn fixed_point_functions_match_reference_values() {
+        assert_close(FixedPoint::from_int(2).sqrt().unwrap(), 2f64.sqrt(), 1e-7);
+        assert_close(FixedPoint::ONE.exp().unwrap(), 1f64.exp(), 1e-7);
+        assert_close(FixedPoint::from_int(-3).exp().unwrap(), (-3f64).exp(), 1e-7);
+        assert_close(FixedPoint::from_int(10).ln().unwrap(), 10f64.ln(), 1e-7);
+        assert_close(
+            FixedPoint::from_ratio(1, 8).ln().unwrap(),
+            0.125f64.ln(),
+            1e-7,
+        );
+    }
+
+    #[test]
+    fn overflowing_arithmetic_fails() {
+        let huge = FixedPoint(i128::MAX / 2);
+        let error = anchor_lang::error::Error::from(CustomError::RatingOverflow);
+        assert_eq!(huge.checked_mul(huge).unwrap_err(), error);
+        assert_eq!(huge.checked_div(FixedPoint(1)).unwrap_err(), error);
+        assert_eq!(
+            FixedPoint::ONE.checked_div(FixedPoint::ZERO).unwrap_err(),
+            error
+        );
+    }
+
+    // the worked example from Glickman's "Example of the Glicko-2 system"
+    #[test]
+    fn matches_glickman_example() {
+        let player = rating(1500, 200);
+        let results = [
+            (rating(1400, 30), FixedPoint::ONE),
+            (rating(1550, 100), FixedPoint::ZERO),
+            (rating(1700, 300), FixedPoint::ZERO),
+        ];
+        let new_rating = player.get_period_rating(&results, 1).unwrap();
+
+        assert_close(new_rating.get_rating(), 1464.06, 0.01);
+        assert_close(new_rating.get_deviation(), 151.52, 0.01);
+        assert_close(new_rating.get_volatility(), 0.05999, 0.00001);
+        assert_eq!(new_rating.games, 3);
+        assert_eq!(new_rating.last_game, 1);
+    }
+
+    #[test]
+    fn converges_for_evenly_matched_established_players() {
+        let player = Rating {
+            volatility: FixedPoint::from_ratio(6, 100).to_raw(),
+            ..rating(1500, 30)
+        };
+        let new_rating = player.get_new_rating(player, FixedPoint::HALF, 1).unwrap();
+
+        assert_eq!(new_rating.get_elo(), 1500);
+        assert_close(new_rating.get_volatility(), 0.06, 0.0001);
+    }
+
+    #[test]
+    fn deviation_grows_with_idle_periods() {
+        let player = Rating {
+            last_game: RATING_PERIOD,
+            ..rating(1500, 50)
+        };
+
+        assert_close(player.get_decayed_deviation(0).unwrap(), 50.0, 0.0);
+        assert_close(
+            player.get_decayed_deviation(RATING_PERIOD).unwrap(),
+            50.0,
+            0.0,
+        );
+
+        // sqrt(50^2 + 100 * (0.06 * 173.7178)^2)
+        let idle = RATING_PERIOD + 100 * RATING_PERIOD;
+        assert_close(player.get_decayed_deviation(idle).unwrap(), 115.60, 0.01);
+
+        // long absences fall back to the deviation of a new player
+        let idle = RATING_PERIOD + 10_000 * RATING_PERIOD;
+        assert_close(
+            player.get_decayed_deviation(idle).unwrap(),
+            DEFAULT_DEVIATION as f64,
+            0.0,
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct RatingRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl RatingRange {
+    pub fn contains(&self, elo: u32) -> bool {
+        self.min <= elo && elo <= self.max
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min <= self.max
+    }
+}
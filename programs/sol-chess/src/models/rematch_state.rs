@@ -0,0 +1,35 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum RematchState {
+    Neither,
+    White,
+    Black,
+    Accepted,
+}
+
+impl RematchState {
+    pub fn color_offered(&self, color: Color) -> bool {
+        if color.is_white() {
+            self == &RematchState::White
+        } else {
+            self == &RematchState::Black
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        if color.is_white() {
+            *self = RematchState::White;
+        } else {
+            *self = RematchState::Black;
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self == &RematchState::Accepted
+    }
+
+    pub fn set_accepted(&mut self) {
+        *self = RematchState::Accepted;
+    }
+}
@@ -16,6 +16,48 @@ impl Square {
         return self.file as usize;
     }
 
+    // rank 0 is the 8th rank, where black starts
+    pub fn get_file_char(&self) -> char {
+        (b'a' + self.file) as char
+    }
+
+    pub fn get_rank_char(&self) -> char {
+        (b'8' - self.rank) as char
+    }
+
+    pub fn to_index(&self) -> u16 {
+        self.rank as u16 * 8 + self.file as u16
+    }
+
+    pub fn from_index(index: u16) -> Square {
+        Square {
+            rank: (index / 8) as u8,
+            file: (index % 8) as u8,
+        }
+    }
+
+    pub fn from_algebraic(algebraic: &str) -> Option<Square> {
+        let bytes = algebraic.as_bytes();
+        if bytes.len() != 2
+            || !(b'a'..=b'h').contains(&bytes[0])
+            || !(b'1'..=b'8').contains(&bytes[1])
+        {
+            return None;
+        }
+        Some(Square {
+            rank: b'8' - bytes[1],
+            file: bytes[0] - b'a',
+        })
+    }
+
+    pub fn to_algebraic(&self) -> String {
+        format!("{}{}", self.get_file_char(), self.get_rank_char())
+    }
+
+    pub fn is_on_board(&self) -> bool {
+        self.rank < 8 && self.file < 8
+    }
+
     pub fn next_rank(&self) -> usize {
         return self.rank as usize + 1;
     }
@@ -476,6 +518,10 @@ impl Square {
 
     pub fn get_pawn_attack_squares(&self, color: Color) -> Vec<Square> {
         let mut squares = vec![];
+        // nothing ahead, e.g. a king on its last rank checked for pawn attacks
+        if self.is_last_rank(color) {
+            return squares;
+        }
 
         if !self.is_leftmost_file_square_relative(color) {
             let left_forward_square = self.get_square_forward_left(color);
@@ -0,0 +1,34 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum TakebackState {
+    Neither,
+    White,
+    Black,
+}
+
+impl TakebackState {
+    pub fn color_requested(&self, color: Color) -> bool {
+        if color.is_white() {
+            self == &TakebackState::White
+        } else {
+            self == &TakebackState::Black
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        if color.is_white() {
+            *self = TakebackState::White;
+        } else {
+            *self = TakebackState::Black;
+        }
+    }
+
+    pub fn is_neither(&self) -> bool {
+        self == &TakebackState::Neither
+    }
+
+    pub fn reset(&mut self) {
+        *self = TakebackState::Neither;
+    }
+}
@@ -0,0 +1,9 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum Termination {
+    Checkmate,
+    Resignation,
+    Timeout,
+    Abandonment,
+}
@@ -0,0 +1,99 @@
+use crate::*;
+
+pub const TIME_CATEGORIES: usize = 5;
+
+// estimated game duration assuming forty moves per player
+const ESTIMATED_MOVES: u64 = 40;
+const BULLET_LIMIT: u64 = 3 * 60;
+const BLITZ_LIMIT: u64 = 8 * 60;
+const RAPID_LIMIT: u64 = 25 * 60;
+const CLASSICAL_LIMIT: u64 = 24 * 60 * 60;
+
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum TimeCategory {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    Correspondence,
+}
+
+impl TimeCategory {
+    pub fn from_time_control(timer: u32, increment: u32) -> Self {
+        let estimated_duration = timer as u64 + ESTIMATED_MOVES * increment as u64;
+        if estimated_duration < BULLET_LIMIT {
+            TimeCategory::Bullet
+        } else if estimated_duration < BLITZ_LIMIT {
+            TimeCategory::Blitz
+        } else if estimated_duration < RAPID_LIMIT {
+            TimeCategory::Rapid
+        } else if estimated_duration < CLASSICAL_LIMIT {
+            TimeCategory::Classical
+        } else {
+            TimeCategory::Correspondence
+        }
+    }
+
+    pub fn get_index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories_change_at_each_cutoff() {
+        for (limit, below, at) in [
+            (BULLET_LIMIT, TimeCategory::Bullet, TimeCategory::Blitz),
+            (BLITZ_LIMIT, TimeCategory::Blitz, TimeCategory::Rapid),
+            (RAPID_LIMIT, TimeCategory::Rapid, TimeCategory::Classical),
+            (
+                CLASSICAL_LIMIT,
+                TimeCategory::Classical,
+                TimeCategory::Correspondence,
+            ),
+        ] {
+            let limit = limit as u32;
+            assert_eq!(TimeCategory::from_time_control(limit - 1, 0), below);
+            assert_eq!(TimeCategory::from_time_control(limit, 0), at);
+        }
+        assert_eq!(TimeCategory::from_time_control(0, 0), TimeCategory::Bullet);
+    }
+
+    #[test]
+    fn increments_count_for_forty_moves() {
+        // 60 + 40 * 2 seconds, then exactly the bullet cutoff
+        assert_eq!(TimeCategory::from_time_control(60, 2), TimeCategory::Bullet);
+        assert_eq!(TimeCategory::from_time_control(60, 3), TimeCategory::Blitz);
+        // 120 + 40 * 8 seconds, then exactly the blitz cutoff
+        assert_eq!(TimeCategory::from_time_control(120, 8), TimeCategory::Blitz);
+        assert_eq!(TimeCategory::from_time_control(120, 9), TimeCategory::Rapid);
+        // 900 + 40 * 10 seconds, then exactly the rapid cutoff
+        assert_eq!(
+            TimeCategory::from_time_control(900, 10),
+            TimeCategory::Rapid
+        );
+        assert_eq!(
+            TimeCategory::from_time_control(1100, 10),
+            TimeCategory::Classical
+        );
+        assert_eq!(
+            TimeCategory::from_time_control(86_000, 9),
+            TimeCategory::Classical
+        );
+        assert_eq!(
+            TimeCategory::from_time_control(86_000, 10),
+            TimeCategory::Correspondence
+        );
+    }
+
+    #[test]
+    fn large_increments_do_not_overflow() {
+        assert_eq!(
+            TimeCategory::from_time_control(u32::MAX, u32::MAX),
+            TimeCategory::Correspondence
+        );
+    }
+}
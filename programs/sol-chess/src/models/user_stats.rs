@@ -0,0 +1,113 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct UserStats {
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+    pub timeouts: u64,
+    pub resignations: u64,
+    pub abandonments: u64,
+    pub total_wagered: u64,
+    pub net_winnings: i64,
+}
+
+impl UserStats {
+    fn record_game(&mut self, wager: u64) {
+        self.games_played += 1;
+        self.total_wagered += wager;
+    }
+
+    pub fn record_win(&mut self, wager: u64) {
+        self.record_game(wager);
+        self.wins += 1;
+        self.net_winnings += wager as i64;
+    }
+
+    pub fn record_loss(&mut self, termination: Termination, wager: u64) {
+        self.record_game(wager);
+        self.losses += 1;
+        self.net_winnings -= wager as i64;
+
+        match termination {
+            Termination::Timeout => self.timeouts += 1,
+            Termination::Resignation => self.resignations += 1,
+            Termination::Abandonment => self.abandonments += 1,
+            _ => {}
+        }
+    }
+
+    pub fn record_draw(&mut self, wager: u64) {
+        self.record_game(wager);
+        self.draws += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_win_counts_the_wager_as_winnings() {
+        let mut stats = UserStats::default();
+        stats.record_win(100);
+
+        assert_eq!(
+            stats,
+            UserStats {
+                games_played: 1,
+                wins: 1,
+                total_wagered: 100,
+                net_winnings: 100,
+                ..UserStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn a_loss_counts_how_the_game_was_lost() {
+        let mut stats = UserStats::default();
+        stats.record_loss(Termination::Checkmate, 0);
+        stats.record_loss(Termination::Timeout, 0);
+        stats.record_loss(Termination::Resignation, 0);
+        stats.record_loss(Termination::Abandonment, 0);
+        stats.record_loss(Termination::Timeout, 0);
+
+        assert_eq!(stats.games_played, 5);
+        assert_eq!(stats.losses, 5);
+        assert_eq!(stats.timeouts, 2);
+        assert_eq!(stats.resignations, 1);
+        assert_eq!(stats.abandonments, 1);
+        assert_eq!(stats.wins, 0);
+    }
+
+    #[test]
+    fn a_draw_wagers_without_winnings() {
+        let mut stats = UserStats::default();
+        stats.record_draw(50);
+
+        assert_eq!(
+            stats,
+            UserStats {
+                games_played: 1,
+                draws: 1,
+                total_wagered: 50,
+                ..UserStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn net_winnings_can_go_negative() {
+        let mut stats = UserStats::default();
+        stats.record_win(100);
+        stats.record_loss(Termination::Resignation, 300);
+        stats.record_draw(200);
+
+        assert_eq!(stats.games_played, 3);
+        assert_eq!((stats.wins, stats.losses, stats.draws), (1, 1, 1));
+        assert_eq!(stats.total_wagered, 600);
+        assert_eq!(stats.net_winnings, -200);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::*;
 
 pub const SEED_GAME: &[u8] = b"game";
+// plies needed before abandonment can be claimed as a win, and as a rated win
+pub const ABANDONMENT_MIN_PLY: u16 = 4;
+pub const ABANDONMENT_RATED_PLY: u16 = 20;
 
 #[account]
 pub struct Game {
@@ -16,8 +19,19 @@ pub struct Game {
     pub enpassant: Option<Square>,
     pub castling_right: CastlingRight,
     pub draw_state: DrawState,
+    pub takeback_state: TakebackState,
+    pub rematch_state: RematchState,
+    // most recent move last
+    pub undo_history: [Option<MoveUndo>; UNDO_HISTORY_SIZE],
     pub game_config: GameConfig,
     pub time_control: TimeControl,
+    pub rating_range: Option<RatingRange>,
+    pub invite: Option<Invite>,
+    // page of the lobby the game's seek is listed on
+    pub lobby_page: u32,
+    pub started_at: i64,
+    // half moves played
+    pub ply: u16,
 }
 
 impl Game {
@@ -28,7 +42,49 @@ impl Game {
         )
     }
 
+    pub fn create(
+        game_config: GameConfig,
+        created_at: i64,
+        owner: Pubkey,
+        id: u64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            created_at,
+            owner,
+            id,
+            bump,
+
+            board: Board::default(),
+            game_state: GameState::Waiting,
+            white: None,
+            black: None,
+            enpassant: None,
+            castling_right: CastlingRight::default(),
+            draw_state: DrawState::default(),
+            takeback_state: TakebackState::Neither,
+            rematch_state: RematchState::Neither,
+            undo_history: [None; UNDO_HISTORY_SIZE],
+            game_config,
+            time_control: game_config.get_time_control(),
+            rating_range: None,
+            invite: None,
+            lobby_page: 0,
+            started_at: 0,
+            ply: 0,
+        }
+    }
+
     pub fn is_valid_move(&self, color: Color, from: Square, to: Square) -> bool {
+        if !from.is_on_board() || !to.is_on_board() {
+            return false;
+        }
+        // only the mover's own pieces
+        let piece = self.board.get_piece(from);
+        if !piece.is_color(color) {
+            return false;
+        }
+
         let valid_moves = self.get_piece_valid_moves(color, from);
         if valid_moves.contains(&to) {
             return true;
@@ -36,9 +92,9 @@ impl Game {
         return false;
     }
 
-    pub fn in_checkmate(&mut self, color: Color) -> bool {
-        if self.not_in_check(color) {
-            return false;
+    pub fn in_checkmate(&mut self, color: Color) -> Result<bool> {
+        if self.not_in_check(color)? {
+            return Ok(false);
         }
 
         let pieces = self.board.get_color_pieces(color);
@@ -47,16 +103,15 @@ impl Game {
             for valid_move in valid_moves {
                 let eaten_piece = self.board.get_piece(valid_move);
                 self.board.move_piece(piece.1, valid_move);
-                if self.not_in_check(color) {
-                    self.board.undo_move(piece.1, valid_move, eaten_piece);
-                    return false;
-                }
-
+                let escapes = self.not_in_check(color);
                 self.board.undo_move(piece.1, valid_move, eaten_piece);
+                if escapes? {
+                    return Ok(false);
+                }
             }
         }
 
-        return true;
+        Ok(true)
     }
 
     pub fn get_piece_valid_moves(&self, color: Color, square: Square) -> Vec<Square> {
@@ -71,13 +126,32 @@ impl Game {
         };
     }
 
-    pub fn not_in_check(&self, color: Color) -> bool {
-        return !self.in_check(color);
+    /// Every move of `color` that does not leave its own king in check, none
+    /// without a king.
+    pub fn get_legal_moves(&self, color: Color) -> Vec<(Square, Square)> {
+        let mut legal_moves = vec![];
+        for (_, from) in self.board.get_color_pieces(color) {
+            for to in self.get_piece_valid_moves(color, from) {
+                let mut game = self.clone();
+                game.move_piece(color, from, to);
+                if game.not_in_check(color).unwrap_or(false) {
+                    legal_moves.push((from, to));
+                }
+            }
+        }
+        legal_moves
     }
 
-    pub fn in_check(&self, color: Color) -> bool {
-        let king_square = self.board.get_king(color).unwrap();
-        return self.board.is_square_attacked(king_square, color);
+    pub fn not_in_check(&self, color: Color) -> Result<bool> {
+        Ok(!self.in_check(color)?)
+    }
+
+    pub fn in_check(&self, color: Color) -> Result<bool> {
+        let king_square = self
+            .board
+            .get_king(color)
+            .ok_or_else(|| error!(CustomError::NoKing))?;
+        Ok(self.board.is_square_attacked(king_square, color))
     }
 
     pub fn get_valid_pawn_moves(&self, color: Color, square: Square) -> Vec<Square> {
@@ -104,7 +178,7 @@ impl Game {
         let pawn_attack_squares = square.get_pawn_attack_squares(color);
         for pawn_attack_square in pawn_attack_squares {
             let piece = self.board.get_piece(pawn_attack_square);
-            if (piece.is_not_empty() && piece.get_color().is_opposite(color))
+            if piece.is_color(color.get_opposite())
                 || (self.enpassant.is_some() && pawn_attack_square == self.enpassant.unwrap())
             {
                 valid_squares.push(pawn_attack_square);
@@ -126,7 +200,7 @@ impl Game {
         // eat
         let parallel_pieces = self.board.get_parallel_pieces(square);
         for parallel_piece in parallel_pieces {
-            if parallel_piece.0.get_color().is_opposite(color) {
+            if parallel_piece.0.is_color(color.get_opposite()) {
                 valid_squares.push(parallel_piece.1);
             }
         }
@@ -139,7 +213,7 @@ impl Game {
 
         for jump in square.get_knight_jumps() {
             let piece = self.board.get_piece(jump);
-            if piece.is_empty() || piece.get_color().is_opposite(color) {
+            if !piece.is_color(color) {
                 valid_squares.push(jump);
             }
         }
@@ -159,7 +233,7 @@ impl Game {
         // eat
         let diagonal_pieces = self.board.get_diagonal_pieces(square);
         for diagonal_piece in diagonal_pieces {
-            if diagonal_piece.0.get_color().is_opposite(color) {
+            if diagonal_piece.0.is_color(color.get_opposite()) {
                 valid_squares.push(diagonal_piece.1);
             }
         }
@@ -182,7 +256,7 @@ impl Game {
         // regular move
         for adjacent_square in square.get_adjacent_squares() {
             let piece = self.board.get_piece(adjacent_square);
-            if piece.is_empty() || piece.get_color().is_opposite(color) {
+            if !piece.is_color(color) {
                 valid_squares.push(adjacent_square);
             }
         }
@@ -202,6 +276,21 @@ impl Game {
 
     pub fn move_piece(&mut self, color: Color, from: Square, to: Square) {
         let current_enpassant = self.enpassant.clone();
+        let piece = self.board.get_piece(from);
+        let captured_square = match current_enpassant {
+            Some(enpassant) if piece.is_pawn() && to == enpassant => to.get_square_backward(color),
+            _ => to,
+        };
+        self.push_undo(MoveUndo {
+            from,
+            to,
+            piece,
+            captured: self.board.get_piece(captured_square),
+            captured_square,
+            castling_right: self.castling_right,
+            enpassant: current_enpassant,
+            time_control: self.time_control,
+        });
         self.reset_enpassant();
 
         match self.board.get_piece(from) {
@@ -238,20 +327,164 @@ impl Game {
         self.board.move_piece(from, to);
     }
 
-    pub fn get_current_player_pubkey(&self) -> Pubkey {
-        if self.game_state.get_current_player_turn().is_white() {
-            self.white.unwrap()
-        } else {
-            self.black.unwrap()
+    /// Standard algebraic notation of a move, without the check suffix. Must
+    /// be called before the move is applied.
+    pub fn get_san(&self, color: Color, from: Square, to: Square) -> String {
+        let piece = self.board.get_piece(from);
+        let is_capture = self.board.get_piece(to).is_not_empty()
+            || (piece.is_pawn() && self.enpassant == Some(to));
+
+        if piece.is_king() && from.is_king_square(color) {
+            if to.is_kingside_castle_square(color) {
+                return "O-O".to_string();
+            } else if to.is_queenside_castle_square(color) {
+                return "O-O-O".to_string();
+            }
         }
+
+        let mut san = String::new();
+        match piece.get_san_letter() {
+            Some(letter) => {
+                san.push(letter);
+
+                // other pieces of the same kind that could also reach `to`
+                let ambiguous: Vec<Square> = self
+                    .board
+                    .get_color_pieces(color)
+                    .into_iter()
+                    .filter(|(other, square)| {
+                        *other == piece
+                            && *square != from
+                            && self.get_piece_valid_moves(color, *square).contains(&to)
+                    })
+                    .map(|(_, square)| square)
+                    .collect();
+                if !ambiguous.is_empty() {
+                    if ambiguous.iter().all(|square| square.file != from.file) {
+                        san.push(from.get_file_char());
+                    } else if ambiguous.iter().all(|square| square.rank != from.rank) {
+                        san.push(from.get_rank_char());
+                    } else {
+                        san.push_str(&from.to_algebraic());
+                    }
+                }
+            }
+            None => {
+                if is_capture {
+                    san.push(from.get_file_char());
+                }
+            }
+        }
+
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&to.to_algebraic());
+
+        if piece.is_pawn() && to.is_last_rank(color) {
+            san.push('=');
+            san.push(color.get_queen().get_san_letter().unwrap());
+        }
+        san
     }
 
-    pub fn get_current_player_color(&self) -> Color {
-        if self.game_state.get_current_player_turn().is_white() {
-            return Color::White;
+    pub fn get_last_move(&self) -> Option<MoveUndo> {
+        self.undo_history[UNDO_HISTORY_SIZE - 1]
+    }
+
+    fn push_undo(&mut self, undo: MoveUndo) {
+        self.undo_history.rotate_left(1);
+        self.undo_history[UNDO_HISTORY_SIZE - 1] = Some(undo);
+    }
+
+    fn pop_undo(&mut self) -> Option<MoveUndo> {
+        let undo = self.undo_history[UNDO_HISTORY_SIZE - 1].take();
+        self.undo_history.rotate_right(1);
+        undo
+    }
+
+    fn undo_last_move(&mut self, current_timestamp: i64) -> Result<()> {
+        let undo = self
+            .pop_undo()
+            .ok_or_else(|| error!(CustomError::NothingToTakeBack))?;
+        let color = undo.piece.get_color()?;
+
+        if undo.is_kingside_castle(color) {
+            self.board.undo_kingside_castle_rook(color);
+        } else if undo.is_queenside_castle(color) {
+            self.board.undo_queenside_castle_rook(color);
+        }
+        self.board.set_piece(Piece::Empty, undo.to);
+        self.board.set_piece(undo.piece, undo.from);
+        self.board.set_piece(undo.captured, undo.captured_square);
+
+        self.castling_right = undo.castling_right;
+        self.enpassant = undo.enpassant;
+        // restore the clocks, the player to move starts thinking again from now
+        self.time_control = undo.time_control;
+        if self.time_control.is_not_first_move() {
+            self.time_control.set_last_move(current_timestamp);
+        }
+
+        self.game_state = self.game_state.next_turn()?;
+        self.ply -= 1;
+        Ok(())
+    }
+
+    // moves taken back when `color` requests a takeback, its own last move and
+    // the reply to it if the opponent has already answered
+    pub fn get_takeback_plies(&self, color: Color) -> Result<usize> {
+        if self.get_current_player_color()? == color {
+            Ok(2)
         } else {
-            return Color::Black;
+            Ok(1)
+        }
+    }
+
+    pub fn can_take_back(&self, color: Color) -> Result<bool> {
+        let plies = self.get_takeback_plies(color)?;
+        Ok(self.undo_history[UNDO_HISTORY_SIZE - plies..]
+            .iter()
+            .all(|undo| undo.is_some()))
+    }
+
+    pub fn take_back(&mut self, color: Color, current_timestamp: i64) -> Result<()> {
+        require!(self.can_take_back(color)?, CustomError::NothingToTakeBack);
+        for _ in 0..self.get_takeback_plies(color)? {
+            self.undo_last_move(current_timestamp)?;
         }
+        self.reset_takeback_state();
+        self.reset_draw_state();
+        Ok(())
+    }
+
+    pub fn allows_takebacks(&self) -> bool {
+        self.game_config.allows_takebacks()
+    }
+
+    pub fn has_requested_takeback(&self, color: Color) -> bool {
+        self.takeback_state.color_requested(color)
+    }
+
+    pub fn request_takeback(&mut self, color: Color) {
+        self.takeback_state.set_color(color);
+    }
+
+    pub fn reset_takeback_state(&mut self) {
+        self.takeback_state.reset();
+    }
+
+    pub fn get_current_player_pubkey(&self) -> Result<Pubkey> {
+        let player = if self.game_state.get_current_player_turn()?.is_white() {
+            self.white
+        } else {
+            self.black
+        };
+        player.ok_or_else(|| error!(CustomError::EmptySeat))
+    }
+
+    pub fn get_current_player_color(&self) -> Result<Color> {
+        self.game_state.get_current_player_turn()
     }
 
     pub fn join_game(&mut self, user: Pubkey, color: Color) {
@@ -274,12 +507,109 @@ impl Game {
         self.white.is_some() && self.black.is_some()
     }
 
-    pub fn start_game(&mut self) {
+    pub fn get_open_color(&self) -> Option<Color> {
+        if self.white.is_some() && self.black.is_none() {
+            Some(Color::Black)
+        } else if self.black.is_some() && self.white.is_none() {
+            Some(Color::White)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_random_colors(&self) -> bool {
+        self.game_config.get_color_preference().is_random()
+    }
+
+    pub fn swap_colors(&mut self) {
+        std::mem::swap(&mut self.white, &mut self.black);
+    }
+
+    pub fn assign_random_colors(&mut self, entropy: [u8; 32]) {
+        if entropy[0] & 1 == 1 {
+            self.swap_colors();
+        }
+    }
+
+    // color offered to joiners, unknown until they join for random games
+    pub fn get_seek_color(&self) -> Option<Color> {
+        if self.has_random_colors() {
+            None
+        } else {
+            self.get_open_color()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.white.is_none() && self.black.is_none()
+    }
+
+    pub fn is_in_rating_range(&self, elo: u32) -> bool {
+        match self.rating_range {
+            Some(rating_range) => rating_range.contains(elo),
+            None => true,
+        }
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.invite.is_some()
+    }
+
+    pub fn is_invited(&self, user: Pubkey, invite_code: Option<&str>) -> bool {
+        match self.invite {
+            Some(invite) => invite.accepts(user, invite_code),
+            None => true,
+        }
+    }
+
+    pub fn has_no_opponent(&self, owner: Pubkey) -> bool {
+        (self.white.is_none() || self.white == Some(owner))
+            && (self.black.is_none() || self.black == Some(owner))
+    }
+
+    pub fn get_seek(&self, game: Pubkey) -> Seek {
+        Seek {
+            game,
+            creator: self.owner,
+            game_config: self.game_config,
+            color: self.get_seek_color(),
+            rating_range: self.rating_range,
+        }
+    }
+
+    pub fn start_game(&mut self, started_at: i64) {
         self.game_state.start_game();
+        self.started_at = started_at;
+    }
+
+    pub fn next_turn(&mut self) -> Result<()> {
+        self.game_state = self.game_state.next_turn()?;
+        self.ply += 1;
+        Ok(())
     }
 
-    pub fn next_turn(&mut self) {
-        self.game_state = self.game_state.next_turn()
+    // either player may abort until both have made their first move. There is
+    // no first move deadline: the side waiting on a first move can already
+    // abort without one, and a stalled game past that is claimed abandoned.
+    pub fn can_abort(&self) -> bool {
+        self.is_still_going() && self.ply < 2
+    }
+
+    pub fn get_last_activity(&self) -> i64 {
+        if self.time_control.is_first_move() {
+            self.started_at
+        } else {
+            self.time_control.last_move
+        }
+    }
+
+    pub fn is_inactive(&self, current_timestamp: i64) -> bool {
+        current_timestamp - self.get_last_activity()
+            > self.game_config.get_inactivity_threshold() as i64
+    }
+
+    pub fn set_aborted(&mut self) {
+        self.game_state.set_aborted();
     }
 
     pub fn set_enpassant(&mut self, square: Square) {
@@ -306,6 +636,10 @@ impl Game {
         self.game_config.get_wager()
     }
 
+    pub fn get_wager_or_zero(&self) -> u64 {
+        self.game_config.get_wager_or_zero()
+    }
+
     pub fn is_in_game(&self, player: Pubkey) -> bool {
         self.white.eq(&Some(player)) || self.black.eq(&Some(player))
     }
@@ -314,11 +648,13 @@ impl Game {
         !self.white.eq(&Some(player)) && !self.black.eq(&Some(player))
     }
 
-    pub fn get_player_color(&self, player: Pubkey) -> Color {
+    pub fn get_player_color(&self, player: Pubkey) -> Result<Color> {
         if self.white.eq(&Some(player)) {
-            return Color::White;
+            Ok(Color::White)
+        } else if self.black.eq(&Some(player)) {
+            Ok(Color::Black)
         } else {
-            return Color::Black;
+            err!(CustomError::NotInGame)
         }
     }
 
@@ -338,12 +674,33 @@ impl Game {
         self.game_state.is_still_going()
     }
 
-    pub fn get_adversary_player(&self, color: Color) -> Pubkey {
-        if color.is_white() {
-            return self.black.unwrap();
+    pub fn is_finished(&self) -> bool {
+        self.game_state.is_finished()
+    }
+
+    pub fn has_offered_rematch(&self, color: Color) -> bool {
+        self.rematch_state.color_offered(color)
+    }
+
+    pub fn can_offer_rematch(&self, color: Color) -> bool {
+        !self.has_offered_rematch(color) && !self.rematch_state.is_accepted()
+    }
+
+    pub fn offer_rematch(&mut self, color: Color) {
+        self.rematch_state.set_color(color);
+    }
+
+    pub fn accept_rematch(&mut self) {
+        self.rematch_state.set_accepted();
+    }
+
+    pub fn get_adversary_player(&self, color: Color) -> Result<Pubkey> {
+        let adversary = if color.is_white() {
+            self.black
         } else {
-            return self.white.unwrap();
-        }
+            self.white
+        };
+        adversary.ok_or_else(|| error!(CustomError::EmptySeat))
     }
 
     pub fn is_draw(&self) -> bool {
@@ -351,7 +708,19 @@ impl Game {
     }
 
     pub fn update_draw_state(&mut self, color: Color) {
-        self.draw_state.update_state(color);
+        self.draw_state.update_state(color, self.ply);
+    }
+
+    pub fn can_offer_draw(&self, color: Color) -> bool {
+        self.draw_state.can_offer(color, self.ply)
+    }
+
+    pub fn decline_draw(&mut self) {
+        self.draw_state.decline();
+    }
+
+    pub fn expire_draw_offer(&mut self, color: Color) {
+        self.draw_state.on_move(color);
     }
 
     pub fn set_draw(&mut self) {
@@ -362,14 +731,29 @@ impl Game {
         self.draw_state.reset();
     }
 
+    pub fn has_offered_draw(&self, color: Color) -> bool {
+        self.draw_state.color_offered(color)
+    }
+
     pub fn has_not_offered_draw(&self, color: Color) -> bool {
-        !self.draw_state.color_offered(color)
+        !self.has_offered_draw(color)
     }
 
     pub fn is_rated(&self) -> bool {
         self.game_config.is_rated()
     }
 
+    // short abandoned games pay out the wager but leave ratings untouched
+    pub fn rates(&self, outcome: Outcome) -> bool {
+        self.is_rated()
+            && (outcome.get_termination() != Some(Termination::Abandonment)
+                || self.ply >= ABANDONMENT_RATED_PLY)
+    }
+
+    pub fn get_time_category(&self) -> TimeCategory {
+        self.game_config.get_time_category()
+    }
+
     pub fn is_first_move(&self) -> bool {
         self.time_control.is_first_move()
     }
@@ -397,6 +781,23 @@ pub trait GameAccount {
         id: u64,
         bump: u8,
     ) -> Result<()>;
+
+    fn list_in_lobby(
+        &mut self,
+        lobby_page: &mut LobbyPage,
+        rating_range: Option<RatingRange>,
+    ) -> Result<()>;
+
+    fn set_invite(&mut self, invite: Invite) -> Result<()>;
+
+    fn settle(
+        &mut self,
+        user: &mut Account<User>,
+        adversary_user: &mut Account<User>,
+        leaderboard: Option<&AccountInfo>,
+        outcome: Outcome,
+        current_timestamp: i64,
+    ) -> Result<()>;
 }
 
 impl GameAccount for Account<'_, Game> {
@@ -408,20 +809,330 @@ impl GameAccount for Account<'_, Game> {
         id: u64,
         bump: u8,
     ) -> Result<()> {
-        self.created_at = created_at;
-        self.owner = owner;
-        self.id = id;
-        self.bump = bump;
-
-        self.board = Board::default();
-        self.game_state = GameState::Waiting;
-        self.white = None;
-        self.black = None;
-        self.enpassant = None;
-        self.castling_right = CastlingRight::default();
-        self.draw_state = DrawState::Neither;
-        self.game_config = game_config;
-        self.time_control = game_config.get_time_control();
+        self.set_inner(Game::create(game_config, created_at, owner, id, bump));
+        Ok(())
+    }
+
+    fn list_in_lobby(
+        &mut self,
+        lobby_page: &mut LobbyPage,
+        rating_range: Option<RatingRange>,
+    ) -> Result<()> {
+        self.rating_range = rating_range;
+        self.lobby_page = lobby_page.page;
+        lobby_page.add_seek(self.get_seek(self.key()));
+        Ok(())
+    }
+
+    fn set_invite(&mut self, invite: Invite) -> Result<()> {
+        self.invite = Some(invite);
         Ok(())
     }
+
+    /// Ends the game with `outcome`: both players get their stats and payout,
+    /// rated games update the ratings and the leaderboard, then `GameSettled`
+    /// is emitted. The leaderboard is only read when the game rates, so
+    /// unrated games settle without it.
+    fn settle(
+        &mut self,
+        user: &mut Account<User>,
+        adversary_user: &mut Account<User>,
+        leaderboard: Option<&AccountInfo>,
+        outcome: Outcome,
+        current_timestamp: i64,
+    ) -> Result<()> {
+        let category = self.get_time_category();
+        let wager = self.get_wager_or_zero();
+        let color = self.get_player_color(user.key())?;
+        let adversary_color = color.get_opposite();
+        let user_rating = user.get_rating(category);
+        let adversary_rating = adversary_user.get_rating(category);
+
+        // checked before anything changes
+        let mut leaderboard = if self.rates(outcome) {
+            let leaderboard_info =
+                leaderboard.ok_or_else(|| error!(CustomError::InvalidLeaderboardAccount))?;
+            require!(
+                leaderboard_info.key() == Leaderboard::pda(category).0
+                    && leaderboard_info.is_writable,
+                CustomError::InvalidLeaderboardAccount
+            );
+            Some(Account::<Leaderboard>::try_from(leaderboard_info)?)
+        } else {
+            None
+        };
+
+        match outcome {
+            Outcome::Win(winner, _) => self.set_winner(winner),
+            Outcome::Draw => self.set_draw(),
+        }
+        user.settle_game(self.key(), color, outcome, wager);
+        adversary_user.settle_game(self.key(), adversary_color, outcome, wager);
+
+        if let Some(leaderboard) = leaderboard.as_mut() {
+            user.rate_against(
+                category,
+                adversary_rating,
+                outcome.get_score(color),
+                current_timestamp,
+            );
+            adversary_user.rate_against(
+                category,
+                user_rating,
+                outcome.get_score(adversary_color),
+                current_timestamp,
+            );
+            leaderboard.update_user(
+                user.key(),
+                user.get_elo(category),
+                user.get_rated_games(category),
+            );
+            leaderboard.update_user(
+                adversary_user.key(),
+                adversary_user.get_elo(category),
+                adversary_user.get_rated_games(category),
+            );
+            leaderboard.exit(&crate::ID)?;
+        }
+
+        emit!(GameSettled::new(
+            self,
+            outcome.get_termination(),
+            PlayerSettlement::new(
+                user.key(),
+                user_rating.get_elo(),
+                user.get_elo(category),
+                outcome.get_balance_delta(color, wager),
+            ),
+            PlayerSettlement::new(
+                adversary_user.key(),
+                adversary_rating.get_elo(),
+                adversary_user.get_elo(category),
+                outcome.get_balance_delta(adversary_color, wager),
+            ),
+            current_timestamp,
+        )?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(algebraic: &str) -> Square {
+        Square::from_algebraic(algebraic).unwrap()
+    }
+
+    fn start_game() -> Game {
+        let game_config = GameConfig {
+            timer: 600,
+            ..GameConfig::default()
+        };
+        let mut game = Game::create(game_config, 0, Pubkey::default(), 0, 0);
+        game.join_game(Pubkey::new_unique(), Color::White);
+        game.join_game(Pubkey::new_unique(), Color::Black);
+        game.start_game(0);
+        game
+    }
+
+    fn new_log() -> MoveLog {
+        MoveLog {
+            game: Pubkey::default(),
+            bump: 0,
+            moves: vec![],
+            timestamps: vec![],
+        }
+    }
+
+    // plays `moves` the way `move_piece` does, a second apart
+    fn play(game: &mut Game, log: &mut MoveLog, moves: &[&str]) {
+        for mv in moves {
+            let timestamp = game.ply as i64 + 1;
+            let color = game.get_current_player_color().unwrap();
+            let (from, to) = (square(&mv[..2]), square(&mv[2..]));
+            game.move_piece(color, from, to);
+            game.next_turn().unwrap();
+            game.reset_takeback_state();
+            game.update_time_control(color, timestamp);
+            log.log_move(from, to, None, timestamp);
+        }
+    }
+
+    // takes back the request of `color` the way `accept_takeback` does, and
+    // checks the result against `before`, the game when it was `color`'s turn
+    fn assert_takes_back_to(game: &mut Game, log: &mut MoveLog, color: Color, before: &Game) {
+        let plies = game.get_takeback_plies(color).unwrap();
+        game.request_takeback(color);
+        game.take_back(color, 100).unwrap();
+        log.log_takeback(plies as u16, 100);
+
+        assert_eq!(game.board.board, before.board.board);
+        assert_eq!(game.castling_right, before.castling_right);
+        assert_eq!(game.enpassant, before.enpassant);
+        assert_eq!(game.game_state, before.game_state);
+        assert_eq!(game.ply, before.ply);
+        assert_eq!(game.takeback_state, TakebackState::Neither);
+        // the clocks are restored, with the player to move thinking from now
+        assert_eq!(
+            game.time_control,
+            TimeControl {
+                last_move: 100,
+                ..before.time_control
+            }
+        );
+
+        assert_eq!(
+            log.decode().unwrap().last(),
+            Some(&LoggedMove::Takeback {
+                plies: plies as u16,
+                timestamp: 100,
+            })
+        );
+        let replayed = log.replay(GameConfig::default()).unwrap();
+        assert_eq!(replayed.last().unwrap().board.board, before.board.board);
+        assert_eq!(replayed.last().unwrap().ply, before.ply);
+    }
+
+    #[test]
+    fn taking_back_a_castle_restores_the_rook_and_rights() {
+        let (mut game, mut log) = (start_game(), new_log());
+        play(
+            &mut game,
+            &mut log,
+            &["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6"],
+        );
+        let before = game.clone();
+
+        play(&mut game, &mut log, &["e1g1"]);
+        assert_eq!(game.board.get_piece(square("f1")), Piece::WhiteRook);
+        assert!(!game.castling_right.has_right(Color::White));
+
+        assert_takes_back_to(&mut game, &mut log, Color::White, &before);
+        assert_eq!(game.board.get_piece(square("e1")), Piece::WhiteKing);
+        assert_eq!(game.board.get_piece(square("h1")), Piece::WhiteRook);
+        assert!(game.castling_right.has_kingside_right(Color::White));
+    }
+
+    #[test]
+    fn taking_back_an_en_passant_capture_restores_the_captured_pawn() {
+        let (mut game, mut log) = (start_game(), new_log());
+        play(&mut game, &mut log, &["e2e4", "a7a6", "e4e5", "d7d5"]);
+        let before = game.clone();
+        assert_eq!(before.enpassant, Some(square("d6")));
+
+        play(&mut game, &mut log, &["e5d6"]);
+        assert_eq!(game.board.get_piece(square("d5")), Piece::Empty);
+
+        assert_takes_back_to(&mut game, &mut log, Color::White, &before);
+        assert_eq!(game.board.get_piece(square("d5")), Piece::BlackPawn);
+        assert_eq!(game.board.get_piece(square("e5")), Piece::WhitePawn);
+        assert_eq!(game.board.get_piece(square("d6")), Piece::Empty);
+    }
+
+    #[test]
+    fn taking_back_a_promotion_restores_the_pawn_and_the_captured_rook() {
+        let (mut game, mut log) = (start_game(), new_log());
+        play(
+            &mut game,
+            &mut log,
+            &[
+                "h2h4", "g7g5", "h4g5", "h7h6", "g5h6", "f8g7", "h6g7", "g8f6",
+            ],
+        );
+        let before = game.clone();
+
+        play(&mut game, &mut log, &["g7h8"]);
+        assert_eq!(game.board.get_piece(square("h8")), Piece::WhiteQueen);
+        assert!(!game.castling_right.has_kingside_right(Color::Black));
+
+        assert_takes_back_to(&mut game, &mut log, Color::White, &before);
+        assert_eq!(game.board.get_piece(square("g7")), Piece::WhitePawn);
+        assert_eq!(game.board.get_piece(square("h8")), Piece::BlackRook);
+        assert!(game.castling_right.has_kingside_right(Color::Black));
+    }
+
+    #[test]
+    fn taking_back_on_your_turn_undoes_the_reply_too() {
+        let (mut game, mut log) = (start_game(), new_log());
+        play(&mut game, &mut log, &["e2e4", "d7d5"]);
+        let before = game.clone();
+
+        play(&mut game, &mut log, &["e4d5", "d8d5"]);
+        assert_eq!(game.get_takeback_plies(Color::White).unwrap(), 2);
+
+        assert_takes_back_to(&mut game, &mut log, Color::White, &before);
+        assert_eq!(game.board.get_piece(square("d5")), Piece::BlackPawn);
+        assert_eq!(game.board.get_piece(square("d8")), Piece::BlackQueen);
+    }
+
+    #[test]
+    fn taking_back_more_than_the_undo_history_is_rejected() {
+        let (mut game, mut log) = (start_game(), new_log());
+        play(&mut game, &mut log, &["e2e4"]);
+        // black, to move, would take back two plies with only one played
+        assert!(!game.can_take_back(Color::Black).unwrap());
+        let error = anchor_lang::error::Error::from(CustomError::NothingToTakeBack);
+        assert_eq!(game.take_back(Color::Black, 100).err(), Some(error));
+        assert_eq!(game.ply, 1);
+
+        play(&mut game, &mut log, &["e7e5", "g1f3"]);
+        game.take_back(Color::Black, 100).unwrap();
+        assert_eq!(game.ply, 1);
+        // the history held two plies, both used up
+        assert!(!game.can_take_back(Color::White).unwrap());
+        assert_eq!(
+            game.take_back(Color::White, 100).err(),
+            Some(anchor_lang::error::Error::from(
+                CustomError::NothingToTakeBack
+            ))
+        );
+        assert_eq!(game.board.get_piece(square("e4")), Piece::WhitePawn);
+    }
+
+    #[test]
+    fn either_player_can_abort_until_both_have_moved() {
+        let (mut game, mut log) = (start_game(), new_log());
+        assert!(game.can_abort());
+
+        // white having moved does not stop it from aborting
+        play(&mut game, &mut log, &["e2e4"]);
+        assert!(game.can_abort());
+
+        play(&mut game, &mut log, &["e7e5"]);
+        assert!(!game.can_abort());
+
+        let mut aborted = start_game();
+        aborted.set_aborted();
+        assert!(!aborted.can_abort());
+    }
+
+    #[test]
+    fn only_players_have_a_color() {
+        let game = start_game();
+        assert_eq!(
+            game.get_player_color(game.white.unwrap()).unwrap(),
+            Color::White
+        );
+        assert_eq!(
+            game.get_player_color(game.black.unwrap()).unwrap(),
+            Color::Black
+        );
+        assert_eq!(
+            game.get_player_color(Pubkey::new_unique()).err(),
+            Some(anchor_lang::error::Error::from(CustomError::NotInGame))
+        );
+    }
+
+    #[test]
+    fn checks_need_a_king() {
+        let mut game = start_game();
+        game.board.set_piece(Piece::Empty, square("e8"));
+
+        let error = || anchor_lang::error::Error::from(CustomError::NoKing);
+        assert_eq!(game.in_check(Color::Black).err(), Some(error()));
+        assert_eq!(game.in_checkmate(Color::Black).err(), Some(error()));
+        assert!(game.get_legal_moves(Color::Black).is_empty());
+        assert!(!game.in_check(Color::White).unwrap());
+    }
 }
@@ -0,0 +1,204 @@
+use crate::*;
+
+pub const SEED_LEADERBOARD: &[u8] = b"leaderboard";
+pub const LEADERBOARD_SIZE: usize = 32;
+
+#[derive(Copy, Clone, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub elo: u32,
+    pub games: u32,
+}
+
+/// Top users of a time category sorted by descending rating, users with the
+/// same rating keep the order in which they reached it.
+#[account]
+pub struct Leaderboard {
+    pub category: TimeCategory,
+    pub bump: u8,
+    pub len: u8,
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE],
+}
+
+impl Leaderboard {
+    pub fn pda(category: TimeCategory) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[SEED_LEADERBOARD, &[category.get_index() as u8]],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_entries(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.len as usize]
+    }
+
+    pub fn get_rank(&self, user: Pubkey) -> Option<usize> {
+        self.get_entries()
+            .iter()
+            .position(|entry| entry.user == user)
+    }
+
+    pub fn remove_user(&mut self, user: Pubkey) {
+        if let Some(rank) = self.get_rank(user) {
+            let len = self.len as usize;
+            self.entries.copy_within(rank + 1..len, rank);
+            self.entries[len - 1] = LeaderboardEntry::default();
+            self.len -= 1;
+        }
+    }
+
+    pub fn update_user(&mut self, user: Pubkey, elo: u32, games: u32) {
+        self.remove_user(user);
+
+        let len = self.len as usize;
+        let rank = self
+            .get_entries()
+            .iter()
+            .position(|entry| entry.elo < elo)
+            .unwrap_or(len);
+        if rank >= LEADERBOARD_SIZE {
+            return;
+        }
+
+        // evict the lowest entry to make room
+        let last = len.min(LEADERBOARD_SIZE - 1);
+        self.entries.copy_within(rank..last, rank + 1);
+        self.entries[rank] = LeaderboardEntry { user, elo, games };
+        self.len = (last + 1) as u8;
+    }
+}
+
+pub trait LeaderboardAccount {
+    fn new(&mut self, category: TimeCategory, bump: u8) -> Result<()>;
+}
+
+impl LeaderboardAccount for Account<'_, Leaderboard> {
+    fn new(&mut self, category: TimeCategory, bump: u8) -> Result<()> {
+        self.category = category;
+        self.bump = bump;
+        self.len = 0;
+        self.entries = [LeaderboardEntry::default(); LEADERBOARD_SIZE];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_leaderboard() -> Leaderboard {
+        Leaderboard {
+            category: TimeCategory::Blitz,
+            bump: 0,
+            len: 0,
+            entries: [LeaderboardEntry::default(); LEADERBOARD_SIZE],
+        }
+    }
+
+    fn users(leaderboard: &Leaderboard) -> Vec<Pubkey> {
+        leaderboard
+            .get_entries()
+            .iter()
+            .map(|entry| entry.user)
+            .collect()
+    }
+
+    #[test]
+    fn users_are_sorted_by_descending_rating() {
+        let mut leaderboard = new_leaderboard();
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        leaderboard.update_user(a, 1500, 1);
+        leaderboard.update_user(b, 1700, 1);
+        leaderboard.update_user(c, 1600, 1);
+
+        assert_eq!(users(&leaderboard), vec![b, c, a]);
+        assert_eq!(leaderboard.get_entries()[1].elo, 1600);
+    }
+
+    #[test]
+    fn equal_ratings_keep_the_order_they_were_reached_in() {
+        let mut leaderboard = new_leaderboard();
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        leaderboard.update_user(a, 1500, 1);
+        leaderboard.update_user(b, 1500, 1);
+        leaderboard.update_user(c, 1500, 1);
+        assert_eq!(users(&leaderboard), vec![a, b, c]);
+
+        // reaching the rating again moves a user behind the others
+        leaderboard.update_user(a, 1500, 2);
+        assert_eq!(users(&leaderboard), vec![b, c, a]);
+    }
+
+    #[test]
+    fn updating_a_user_moves_their_entry() {
+        let mut leaderboard = new_leaderboard();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        leaderboard.update_user(a, 1600, 1);
+        leaderboard.update_user(b, 1500, 1);
+
+        leaderboard.update_user(b, 1650, 2);
+
+        assert_eq!(leaderboard.len, 2);
+        assert_eq!(users(&leaderboard), vec![b, a]);
+        assert_eq!(
+            leaderboard.get_entries()[0],
+            LeaderboardEntry {
+                user: b,
+                elo: 1650,
+                games: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn a_full_leaderboard_evicts_the_lowest_entry() {
+        let mut leaderboard = new_leaderboard();
+        let ranked: Vec<Pubkey> = (0..LEADERBOARD_SIZE)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for (index, user) in ranked.iter().enumerate() {
+            leaderboard.update_user(*user, 2000 - index as u32, 1);
+        }
+        assert_eq!(leaderboard.len as usize, LEADERBOARD_SIZE);
+
+        let newcomer = Pubkey::new_unique();
+        leaderboard.update_user(newcomer, 1990, 1);
+        assert_eq!(leaderboard.len as usize, LEADERBOARD_SIZE);
+        assert_eq!(leaderboard.get_rank(newcomer), Some(11));
+        assert_eq!(leaderboard.get_rank(ranked[LEADERBOARD_SIZE - 1]), None);
+
+        // too low a rating for a full leaderboard leaves it unchanged
+        let low = Pubkey::new_unique();
+        leaderboard.update_user(low, 1000, 1);
+        assert_eq!(leaderboard.get_rank(low), None);
+        assert_eq!(leaderboard.len as usize, LEADERBOARD_SIZE);
+    }
+
+    #[test]
+    fn a_falling_user_drops_out_once_overtaken() {
+        let mut leaderboard = new_leaderboard();
+        let ranked: Vec<Pubkey> = (0..LEADERBOARD_SIZE)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for (index, user) in ranked.iter().enumerate() {
+            leaderboard.update_user(*user, 2000 - index as u32, 1);
+        }
+
+        // the table does not know the users below it, so the fallen user stays last
+        leaderboard.update_user(ranked[0], 1000, 2);
+        assert_eq!(leaderboard.get_rank(ranked[0]), Some(LEADERBOARD_SIZE - 1));
+        assert_eq!(leaderboard.len as usize, LEADERBOARD_SIZE);
+
+        leaderboard.update_user(Pubkey::new_unique(), 1001, 1);
+        assert_eq!(leaderboard.get_rank(ranked[0]), None);
+        assert_eq!(&users(&leaderboard)[..LEADERBOARD_SIZE - 1], &ranked[1..]);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+pub const SEED_LOBBY_PAGE: &[u8] = b"lobby_page";
+pub const LOBBY_PAGE_SIZE: usize = 16;
+
+/// Open game waiting for an opponent, `color` is the seat left to the joiner
+/// or `None` when both are open.
+#[derive(Copy, Clone, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct Seek {
+    pub game: Pubkey,
+    pub creator: Pubkey,
+    pub game_config: GameConfig,
+    pub color: Option<Color>,
+    pub rating_range: Option<RatingRange>,
+}
+
+#[account]
+pub struct LobbyPage {
+    pub page: u32,
+    pub bump: u8,
+    pub len: u8,
+    pub seeks: [Seek; LOBBY_PAGE_SIZE],
+}
+
+impl LobbyPage {
+    pub fn pda(page: u32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SEED_LOBBY_PAGE, &page.to_be_bytes()], &crate::ID)
+    }
+
+    pub fn get_seeks(&self) -> &[Seek] {
+        &self.seeks[..self.len as usize]
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len as usize == LOBBY_PAGE_SIZE
+    }
+
+    pub fn add_seek(&mut self, seek: Seek) {
+        self.seeks[self.len as usize] = seek;
+        self.len += 1;
+    }
+
+    pub fn update_seek_color(&mut self, game: Pubkey, color: Option<Color>) {
+        let len = self.len as usize;
+        if let Some(seek) = self.seeks[..len].iter_mut().find(|seek| seek.game == game) {
+            seek.color = color;
+        }
+    }
+
+    pub fn remove_seek(&mut self, game: Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self.get_seeks().iter().position(|seek| seek.game == game) {
+            self.seeks.copy_within(index + 1..len, index);
+            self.seeks[len - 1] = Seek::default();
+            self.len -= 1;
+        }
+    }
+}
+
+pub trait LobbyPageAccount {
+    fn new(&mut self, page: u32, bump: u8) -> Result<()>;
+}
+
+impl LobbyPageAccount for Account<'_, LobbyPage> {
+    fn new(&mut self, page: u32, bump: u8) -> Result<()> {
+        self.page = page;
+        self.bump = bump;
+        self.len = 0;
+        self.seeks = [Seek::default(); LOBBY_PAGE_SIZE];
+        Ok(())
+    }
+}
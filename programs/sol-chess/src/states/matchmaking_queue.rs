@@ -0,0 +1,177 @@
+use crate::*;
+
+pub const SEED_MATCHMAKING_QUEUE: &[u8] = b"matchmaking_queue";
+pub const MATCHMAKING_QUEUE_SIZE: usize = 32;
+
+// the accepted rating gap starts at `BASE_RATING_WINDOW` and widens by
+// `RATING_WINDOW_GROWTH` every `RATING_WINDOW_INTERVAL` seconds spent waiting
+pub const BASE_RATING_WINDOW: u32 = 100;
+pub const RATING_WINDOW_GROWTH: u32 = 25;
+pub const RATING_WINDOW_INTERVAL: i64 = 10;
+pub const MAX_RATING_WINDOW: u32 = 1000;
+pub const QUEUE_KEY_LEN: usize = 24;
+
+#[derive(Copy, Clone, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct QueueEntry {
+    pub user: Pubkey,
+    pub elo: u32,
+    pub enqueued_at: i64,
+}
+
+impl QueueEntry {
+    pub fn get_rating_window(&self, current_timestamp: i64) -> u32 {
+        let intervals = (current_timestamp - self.enqueued_at).max(0) / RATING_WINDOW_INTERVAL;
+        let growth = (intervals as u64 * RATING_WINDOW_GROWTH as u64).min(MAX_RATING_WINDOW as u64);
+        (BASE_RATING_WINDOW + growth as u32).min(MAX_RATING_WINDOW)
+    }
+
+    pub fn is_compatible(&self, other: &QueueEntry, current_timestamp: i64) -> bool {
+        let rating_gap = self.elo.abs_diff(other.elo);
+        rating_gap <= self.get_rating_window(current_timestamp)
+            && rating_gap <= other.get_rating_window(current_timestamp)
+    }
+}
+
+#[account]
+pub struct MatchmakingQueue {
+    pub game_config: GameConfig,
+    pub bump: u8,
+    pub len: u8,
+    pub entries: [QueueEntry; MATCHMAKING_QUEUE_SIZE],
+}
+
+impl MatchmakingQueue {
+    pub fn pda(game_config: &GameConfig) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[SEED_MATCHMAKING_QUEUE, &Self::get_key(game_config)],
+            &crate::ID,
+        )
+    }
+
+    /// Seed of the queue of `game_config`, made of every field but the color,
+    /// which matchmaking assigns. Options carry a tag byte so that `None` and
+    /// `Some(0)` get different queues.
+    pub fn get_key(game_config: &GameConfig) -> [u8; QUEUE_KEY_LEN] {
+        let mut key = [0; QUEUE_KEY_LEN];
+        key[0..4].copy_from_slice(&game_config.timer.to_be_bytes());
+        key[4..8].copy_from_slice(&game_config.increment.to_be_bytes());
+        key[8] = game_config.is_rated as u8;
+        if let Some(wager) = game_config.wager {
+            key[9] = 1;
+            key[10..18].copy_from_slice(&wager.to_be_bytes());
+        }
+        key[18] = game_config.allow_takebacks as u8;
+        if let Some(inactivity_threshold) = game_config.inactivity_threshold {
+            key[19] = 1;
+            key[20..24].copy_from_slice(&inactivity_threshold.to_be_bytes());
+        }
+        key
+    }
+
+    pub fn get_entries(&self) -> &[QueueEntry] {
+        &self.entries[..self.len as usize]
+    }
+
+    pub fn get_entry(&self, user: Pubkey) -> Option<QueueEntry> {
+        self.get_entries()
+            .iter()
+            .find(|entry| entry.user == user)
+            .copied()
+    }
+
+    pub fn is_queued(&self, user: Pubkey) -> bool {
+        self.get_entry(user).is_some()
+    }
+
+    pub fn is_not_queued(&self, user: Pubkey) -> bool {
+        self.get_entry(user).is_none()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len as usize == MATCHMAKING_QUEUE_SIZE
+    }
+
+    pub fn enqueue(&mut self, user: Pubkey, elo: u32, current_timestamp: i64) {
+        self.entries[self.len as usize] = QueueEntry {
+            user,
+            elo,
+            enqueued_at: current_timestamp,
+        };
+        self.len += 1;
+    }
+
+    pub fn dequeue(&mut self, user: Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self
+            .get_entries()
+            .iter()
+            .position(|entry| entry.user == user)
+        {
+            self.entries.copy_within(index + 1..len, index);
+            self.entries[len - 1] = QueueEntry::default();
+            self.len -= 1;
+        }
+    }
+
+    pub fn has_wager(&self) -> bool {
+        self.game_config.has_wager()
+    }
+
+    pub fn get_wager(&self) -> u64 {
+        self.game_config.get_wager()
+    }
+
+    pub fn get_time_category(&self) -> TimeCategory {
+        self.game_config.get_time_category()
+    }
+}
+
+pub trait MatchmakingQueueAccount {
+    fn new(&mut self, game_config: GameConfig, bump: u8) -> Result<()>;
+}
+
+impl MatchmakingQueueAccount for Account<'_, MatchmakingQueue> {
+    fn new(&mut self, game_config: GameConfig, bump: u8) -> Result<()> {
+        self.game_config = game_config;
+        self.bump = bump;
+        self.len = 0;
+        self.entries = [QueueEntry::default(); MATCHMAKING_QUEUE_SIZE];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queues_are_keyed_by_every_field_but_the_color() {
+        let game_config = GameConfig {
+            timer: 300,
+            ..GameConfig::default()
+        };
+        let key = MatchmakingQueue::get_key(&game_config);
+
+        let free_wager = GameConfig {
+            wager: Some(0),
+            ..game_config
+        };
+        let zero_threshold = GameConfig {
+            inactivity_threshold: Some(0),
+            ..game_config
+        };
+        let takebacks = GameConfig {
+            allow_takebacks: true,
+            ..game_config
+        };
+        for other in [free_wager, zero_threshold, takebacks] {
+            assert_ne!(MatchmakingQueue::get_key(&other), key);
+        }
+
+        let white = GameConfig {
+            color: ColorPreference::White,
+            ..game_config
+        };
+        assert_eq!(MatchmakingQueue::get_key(&white), key);
+    }
+}
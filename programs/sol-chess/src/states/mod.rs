@@ -1,5 +1,13 @@
 pub mod game;
+pub mod leaderboard;
+pub mod lobby_page;
+pub mod matchmaking_queue;
+pub mod move_log;
 pub mod user;
 
 pub use game::*;
+pub use leaderboard::*;
+pub use lobby_page::*;
+pub use matchmaking_queue::*;
+pub use move_log::*;
 pub use user::*;
@@ -0,0 +1,301 @@
+use crate::*;
+
+pub const SEED_MOVE_LOG: &[u8] = b"move_log";
+
+// a ply is packed as from (6 bits) | to (6 bits) | promotion (3 bits) | takeback (1 bit),
+// takeback entries store the number of plies taken back in the low bits
+const SQUARE_MASK: u16 = 0b11_1111;
+const TO_SHIFT: u16 = 6;
+const PROMOTION_SHIFT: u16 = 12;
+const PROMOTION_MASK: u16 = 0b111;
+const TAKEBACK_FLAG: u16 = 1 << 15;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LoggedMove {
+    Move {
+        from: Square,
+        to: Square,
+        promotion: Option<Piece>,
+        timestamp: i64,
+    },
+    Takeback {
+        plies: u16,
+        timestamp: i64,
+    },
+}
+
+/// Append-only record of every ply of a game, grown with `realloc` as moves
+/// are made so that disputed games can be audited.
+#[account]
+pub struct MoveLog {
+    pub game: Pubkey,
+    pub bump: u8,
+    pub moves: Vec<u16>,
+    pub timestamps: Vec<i64>,
+}
+
+impl MoveLog {
+    pub fn pda(game: Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SEED_MOVE_LOG, game.as_ref()], &crate::ID)
+    }
+
+    pub fn space(len: usize) -> usize {
+        8 + 32 + 1 + 4 + 4 + len * (size_of::<u16>() + size_of::<i64>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    fn encode_promotion(promotion: Option<Piece>) -> u16 {
+        match promotion {
+            Some(piece) if piece.is_queen() => 1,
+            Some(piece) if piece.is_rook() => 2,
+            Some(piece) if piece.is_bishop() => 3,
+            Some(piece) if piece.is_knight() => 4,
+            _ => 0,
+        }
+    }
+
+    fn decode_promotion(bits: u16, color: Color) -> Option<Piece> {
+        let white = color.is_white();
+        match bits {
+            1 if white => Some(Piece::WhiteQueen),
+            1 => Some(Piece::BlackQueen),
+            2 if white => Some(Piece::WhiteRook),
+            2 => Some(Piece::BlackRook),
+            3 if white => Some(Piece::WhiteBishop),
+            3 => Some(Piece::BlackBishop),
+            4 if white => Some(Piece::WhiteKnight),
+            4 => Some(Piece::BlackKnight),
+            _ => None,
+        }
+    }
+
+    pub fn encode_move(from: Square, to: Square, promotion: Option<Piece>) -> u16 {
+        from.to_index()
+            | to.to_index() << TO_SHIFT
+            | Self::encode_promotion(promotion) << PROMOTION_SHIFT
+    }
+
+    pub fn log_move(&mut self, from: Square, to: Square, promotion: Option<Piece>, timestamp: i64) {
+        self.moves.push(Self::encode_move(from, to, promotion));
+        self.timestamps.push(timestamp);
+    }
+
+    pub fn log_takeback(&mut self, plies: u16, timestamp: i64) {
+        self.moves.push(TAKEBACK_FLAG | plies);
+        self.timestamps.push(timestamp);
+    }
+
+    /// Decodes every entry, following takebacks to know whose turn each ply
+    /// was so promotions get the right color. Fails on takebacks of more
+    /// plies than were played.
+    pub fn decode(&self) -> Result<Vec<LoggedMove>> {
+        let mut ply: u16 = 0;
+        self.moves
+            .iter()
+            .zip(self.timestamps.iter())
+            .map(|(&entry, &timestamp)| {
+                if entry & TAKEBACK_FLAG != 0 {
+                    let plies = entry & !TAKEBACK_FLAG;
+                    ply = ply
+                        .checked_sub(plies)
+                        .ok_or_else(|| error!(CustomError::InvalidMoveLog))?;
+                    return Ok(LoggedMove::Takeback { plies, timestamp });
+                }
+
+                let color = if ply & 1 == 0 {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                ply += 1;
+                Ok(LoggedMove::Move {
+                    from: Square::from_index(entry & SQUARE_MASK),
+                    to: Square::from_index(entry >> TO_SHIFT & SQUARE_MASK),
+                    promotion: Self::decode_promotion(
+                        entry >> PROMOTION_SHIFT & PROMOTION_MASK,
+                        color,
+                    ),
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+
+    /// Replays the log through `Game::move_piece` and returns the game after
+    /// every entry, starting with the initial position.
+    pub fn replay(&self, game_config: GameConfig) -> Result<Vec<Game>> {
+        let mut game = Game::create(game_config, 0, Pubkey::default(), 0, 0);
+        game.start_game(0);
+
+        // positions reachable by a takeback
+        let mut history = vec![game.clone()];
+        let mut positions = vec![game];
+        for logged_move in self.decode()? {
+            match logged_move {
+                LoggedMove::Move {
+                    from,
+                    to,
+                    promotion,
+                    ..
+                } => {
+                    let mut game = history.last().unwrap().clone();
+                    let color = game.get_current_player_color()?;
+                    game.move_piece(color, from, to);
+                    // the logged piece wins over the queen `move_piece` promotes to
+                    if let Some(piece) = promotion {
+                        game.board.set_piece(piece, to);
+                    }
+                    game.next_turn()?;
+                    history.push(game.clone());
+                    positions.push(game);
+                }
+                LoggedMove::Takeback { plies, .. } => {
+                    history.truncate(history.len() - plies as usize);
+                    positions.push(history.last().unwrap().clone());
+                }
+            }
+        }
+        Ok(positions)
+    }
+}
+
+pub trait MoveLogAccount {
+    fn new(&mut self, game: Pubkey, bump: u8) -> Result<()>;
+}
+
+impl MoveLogAccount for Account<'_, MoveLog> {
+    fn new(&mut self, game: Pubkey, bump: u8) -> Result<()> {
+        self.game = game;
+        self.bump = bump;
+        self.moves = vec![];
+        self.timestamps = vec![];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(algebraic: &str) -> Square {
+        Square::from_algebraic(algebraic).unwrap()
+    }
+
+    fn new_log() -> MoveLog {
+        MoveLog {
+            game: Pubkey::default(),
+            bump: 0,
+            moves: vec![],
+            timestamps: vec![],
+        }
+    }
+
+    // 1. h4 g5 2. hxg5 h6 3. gxh6 Bg7, two plies taken back and replayed,
+    // then 4. hxg7 Nf6 5. gxh8=N
+    fn under_promotion_log() -> (MoveLog, Vec<LoggedMove>) {
+        let moves = [
+            ("h2", "h4"),
+            ("g7", "g5"),
+            ("h4", "g5"),
+            ("h7", "h6"),
+            ("g5", "h6"),
+            ("f8", "g7"),
+        ];
+        let mut log = new_log();
+        let mut expected = vec![];
+        let mut timestamp = 0;
+        let mut push = |log: &mut MoveLog, from: &str, to: &str, promotion: Option<Piece>| {
+            timestamp += 1;
+            log.log_move(square(from), square(to), promotion, timestamp);
+            expected.push(LoggedMove::Move {
+                from: square(from),
+                to: square(to),
+                promotion,
+                timestamp,
+            });
+        };
+        for (from, to) in moves {
+            push(&mut log, from, to, None);
+        }
+        log.log_takeback(2, 10);
+        push(&mut log, "g5", "h6", None);
+        push(&mut log, "f8", "g7", None);
+        push(&mut log, "h6", "g7", None);
+        push(&mut log, "g8", "f6", None);
+        push(&mut log, "g7", "h8", Some(Piece::WhiteKnight));
+
+        expected.insert(
+            6,
+            LoggedMove::Takeback {
+                plies: 2,
+                timestamp: 10,
+            },
+        );
+        (log, expected)
+    }
+
+    #[test]
+    fn decode_round_trips_moves_promotions_and_takebacks() {
+        let (log, expected) = under_promotion_log();
+
+        assert_eq!(log.len(), expected.len());
+        assert_eq!(log.decode().unwrap(), expected);
+    }
+
+    #[test]
+    fn promotions_decode_with_the_color_to_move() {
+        let mut log = new_log();
+        log.log_move(square("e2"), square("e4"), None, 1);
+        log.log_move(square("b2"), square("a1"), Some(Piece::BlackRook), 2);
+        log.log_takeback(1, 3);
+        log.log_move(square("b2"), square("a1"), Some(Piece::BlackBishop), 4);
+
+        let promotions: Vec<Option<Piece>> = log
+            .decode()
+            .unwrap()
+            .into_iter()
+            .filter_map(|logged_move| match logged_move {
+                LoggedMove::Move { promotion, .. } => Some(promotion),
+                LoggedMove::Takeback { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            promotions,
+            vec![None, Some(Piece::BlackRook), Some(Piece::BlackBishop)]
+        );
+    }
+
+    #[test]
+    fn replay_follows_takebacks_and_under_promotions() {
+        let (log, _) = under_promotion_log();
+        let positions = log.replay(GameConfig::default()).unwrap();
+
+        assert_eq!(positions.len(), log.len() + 1);
+        // the takeback returns to the position after 2... h6
+        assert_eq!(positions[7].board.board, positions[4].board.board);
+        assert_eq!(positions[7].ply, 4);
+
+        let last = positions.last().unwrap();
+        assert_eq!(last.board.get_piece(square("h8")), Piece::WhiteKnight);
+        assert_eq!(last.board.get_piece(square("g7")), Piece::Empty);
+        assert_eq!(last.ply, 9);
+    }
+
+    #[test]
+    fn takebacks_past_the_start_are_rejected() {
+        let mut log = new_log();
+        log.log_move(square("e2"), square("e4"), None, 1);
+        log.log_takeback(2, 2);
+
+        let error = anchor_lang::error::Error::from(CustomError::InvalidMoveLog);
+        assert_eq!(log.decode().unwrap_err(), error);
+        assert_eq!(log.replay(GameConfig::default()).err(), Some(error));
+    }
+}
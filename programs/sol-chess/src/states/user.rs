@@ -1,13 +1,17 @@
 use crate::*;
 
 pub const SEED_USER: &[u8] = b"user";
+pub const MAX_ACTIVE_GAMES: usize = 8;
 
 #[account]
 pub struct User {
-    pub current_game: Option<Pubkey>,
-    pub elo: u32,
-    pub games: u64,
+    pub active_games: [Option<Pubkey>; MAX_ACTIVE_GAMES],
+    pub max_active_games: u8,
+    pub ratings: [Rating; TIME_CATEGORIES],
+    // games created, also used as the seed of the next game pda
+    pub games_created: u64,
     pub balance: u64,
+    pub stats: UserStats,
 }
 
 impl User {
@@ -15,20 +19,53 @@ impl User {
         Pubkey::find_program_address(&[SEED_USER, owner.as_ref()], &crate::ID)
     }
 
-    pub fn set_game(&mut self, game: Pubkey) {
-        self.current_game = Some(game);
+    pub fn add_game(&mut self, game: Pubkey) {
+        if self.has_game(game) {
+            return;
+        }
+        if let Some(slot) = self.active_games.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(game);
+        }
+    }
+
+    pub fn remove_game(&mut self, game: Pubkey) {
+        for slot in self.active_games.iter_mut() {
+            if slot == &Some(game) {
+                *slot = None;
+            }
+        }
+    }
+
+    pub fn has_game(&self, game: Pubkey) -> bool {
+        self.active_games.contains(&Some(game))
+    }
+
+    pub fn get_active_games(&self) -> Vec<Pubkey> {
+        self.active_games.iter().flatten().copied().collect()
+    }
+
+    pub fn get_active_games_count(&self) -> usize {
+        self.active_games.iter().flatten().count()
+    }
+
+    pub fn can_join_game(&self) -> bool {
+        self.get_active_games_count() < self.max_active_games as usize
+    }
+
+    pub fn set_max_active_games(&mut self, max_active_games: u8) {
+        self.max_active_games = max_active_games;
     }
 
-    pub fn increment_games(&mut self) {
-        self.games += 1;
+    pub fn increment_games_created(&mut self) {
+        self.games_created += 1;
     }
 
     pub fn in_game(&self) -> bool {
-        self.current_game.is_some()
+        self.get_active_games_count() > 0
     }
 
     pub fn not_in_game(&self) -> bool {
-        self.current_game.is_none()
+        self.get_active_games_count() == 0
     }
 
     pub fn increase_balance(&mut self, amount: u64) {
@@ -47,28 +84,66 @@ impl User {
         }
     }
 
-    pub fn get_elo(&self) -> u32 {
-        self.elo
+    /// Closes a finished game for the player of `color`: its stats and the
+    /// payout of the escrowed wagers.
+    pub fn settle_game(&mut self, game: Pubkey, color: Color, outcome: Outcome, wager: u64) {
+        self.remove_game(game);
+        match outcome {
+            Outcome::Win(winner, _) if winner == color => {
+                self.record_win(wager);
+                self.increase_balance(wager * 2);
+            }
+            Outcome::Win(_, termination) => self.record_loss(termination, wager),
+            Outcome::Draw => {
+                self.record_draw(wager);
+                self.increase_balance(wager);
+            }
+        }
+    }
+
+    pub fn record_win(&mut self, wager: u64) {
+        self.stats.record_win(wager);
+    }
+
+    pub fn record_loss(&mut self, termination: Termination, wager: u64) {
+        self.stats.record_loss(termination, wager);
     }
 
-    pub fn get_expected_score(&self, adversary_elo: u32) -> f64 {
-        1.0 / (1.0 + 10f64.powf((adversary_elo as f64 - self.elo as f64) / 400.0))
+    pub fn record_draw(&mut self, wager: u64) {
+        self.stats.record_draw(wager);
     }
 
-    pub fn get_new_elo(&self, adversary_elo: u32, score: f64) -> u32 {
-        self.elo + (40.0 * (score - self.get_expected_score(adversary_elo))) as u32
+    pub fn get_rating(&self, category: TimeCategory) -> Rating {
+        self.ratings[category.get_index()]
     }
 
-    pub fn won_against(&mut self, adversary_elo: u32) {
-        self.elo = self.get_new_elo(adversary_elo, 1.0);
+    pub fn get_elo(&self, category: TimeCategory) -> u32 {
+        self.get_rating(category).get_elo()
     }
 
-    pub fn draw_against(&mut self, adversary_elo: u32) {
-        self.elo = self.get_new_elo(adversary_elo, 0.5);
+    pub fn get_rated_games(&self, category: TimeCategory) -> u32 {
+        self.get_rating(category).games
     }
 
-    pub fn lost_against(&mut self, adversary_elo: u32) {
-        self.elo = self.get_new_elo(adversary_elo, 0.0);
+    pub fn set_rating(&mut self, category: TimeCategory, rating: Rating) {
+        self.ratings[category.get_index()] = rating;
+    }
+
+    // `score` is 1 for a win, 1/2 for a draw and 0 for a loss. A rating that
+    // cannot be computed stays as it was rather than blocking the settlement.
+    pub fn rate_against(
+        &mut self,
+        category: TimeCategory,
+        adversary_rating: Rating,
+        score: FixedPoint,
+        current_timestamp: i64,
+    ) {
+        if let Ok(rating) =
+            self.get_rating(category)
+                .get_new_rating(adversary_rating, score, current_timestamp)
+        {
+            self.set_rating(category, rating);
+        }
     }
 }
 
@@ -78,10 +153,56 @@ pub trait UserAccount {
 
 impl UserAccount for Account<'_, User> {
     fn new(&mut self) -> Result<()> {
-        self.current_game = None;
-        self.elo = 800;
-        self.games = 0;
+        self.active_games = [None; MAX_ACTIVE_GAMES];
+        self.max_active_games = MAX_ACTIVE_GAMES as u8;
+        self.ratings = [Rating::default(); TIME_CATEGORIES];
+        self.games_created = 0;
         self.balance = 0;
+        self.stats = UserStats::default();
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_user() -> User {
+        User {
+            active_games: [None; MAX_ACTIVE_GAMES],
+            max_active_games: MAX_ACTIVE_GAMES as u8,
+            ratings: [Rating::default(); TIME_CATEGORIES],
+            games_created: 0,
+            balance: 0,
+            stats: UserStats::default(),
+        }
+    }
+
+    #[test]
+    fn rating_against_updates_the_category() {
+        let mut user = new_user();
+        user.rate_against(TimeCategory::Blitz, Rating::default(), FixedPoint::ONE, 1);
+
+        assert!(user.get_elo(TimeCategory::Blitz) > DEFAULT_RATING as u32);
+        assert_eq!(user.get_rated_games(TimeCategory::Blitz), 1);
+        assert_eq!(user.get_rating(TimeCategory::Rapid), Rating::default());
+    }
+
+    #[test]
+    fn a_failed_rating_update_keeps_the_rating() {
+        let mut user = new_user();
+        let broken = Rating {
+            deviation: 0,
+            volatility: i64::MAX,
+            ..Rating::default()
+        };
+        user.set_rating(TimeCategory::Blitz, broken);
+        assert!(broken
+            .get_new_rating(Rating::default(), FixedPoint::ONE, 1)
+            .is_err());
+
+        user.rate_against(TimeCategory::Blitz, Rating::default(), FixedPoint::ONE, 1);
+
+        assert_eq!(user.get_rating(TimeCategory::Blitz), broken);
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use {
+    common::*,
+    sol_chess::{CustomError, GameState},
+    solana_sdk::signature::Signer,
+};
+
+#[tokio::test]
+async fn deposit_and_withdraw_move_lamports_through_the_vault() {
+    let mut env = TestEnv::new(1, blitz_config(None).get_time_category()).await;
+    let player = env.player(0);
+
+    env.deposit(&player, 3 * WAGER).await;
+    assert_eq!(env.get_user(player.pubkey()).await.balance, 3 * WAGER);
+    assert_eq!(
+        env.get_lamports(get_vault()).await,
+        VAULT_LAMPORTS + 3 * WAGER
+    );
+
+    let wallet = env.get_lamports(player.pubkey()).await;
+    env.withdraw(&player, WAGER).await.unwrap();
+    assert_eq!(env.get_user(player.pubkey()).await.balance, 2 * WAGER);
+    assert_eq!(
+        env.get_lamports(get_vault()).await,
+        VAULT_LAMPORTS + 2 * WAGER
+    );
+    // the withdrawal minus the transaction fee
+    let received = env.get_lamports(player.pubkey()).await - wallet;
+    assert!(received > WAGER - 10_000 && received <= WAGER);
+
+    assert_program_error(
+        env.withdraw(&player, 3 * WAGER).await,
+        CustomError::InsufficientBalance,
+    );
+    assert_eq!(env.get_user(player.pubkey()).await.balance, 2 * WAGER);
+}
+
+#[tokio::test]
+async fn joining_without_the_wager_fails() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+
+    assert_program_error(
+        env.join_game(&black, game).await,
+        CustomError::InsufficientBalance,
+    );
+    assert_eq!(env.get_game(game).await.game_state, GameState::Waiting);
+}
+
+#[tokio::test]
+async fn leaving_an_unstarted_game_refunds_the_wager() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    assert_eq!(env.get_user(white.pubkey()).await.balance, 0);
+
+    env.leave_game(&white, game).await.unwrap();
+
+    let user = env.get_user(white.pubkey()).await;
+    assert_eq!(user.balance, WAGER);
+    assert!(user.get_active_games().is_empty());
+    assert!(env.get_game(game).await.is_empty());
+
+    assert_program_error(env.leave_game(&black, game).await, CustomError::NotInGame);
+}
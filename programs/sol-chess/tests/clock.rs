@@ -0,0 +1,179 @@
+mod common;
+
+use {
+    anchor_lang::prelude::Pubkey,
+    common::*,
+    sol_chess::{accounts, instruction, CustomError, GameConfig, GameState, DEFAULT_RATING},
+    solana_sdk::{signature::Signer, sysvar},
+};
+
+// a game where white has 30 seconds left and black, to move, a minute
+async fn start_clocked_game(env: &mut TestEnv, game_config: GameConfig) -> Pubkey {
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+
+    for (ply, mv) in ["e2e4", "e7e5", "g1f3", "b8c6"].iter().enumerate() {
+        let player = if ply % 2 == 0 { &white } else { &black };
+        env.play(player, game, mv).await.unwrap();
+    }
+
+    env.warp(30).await;
+    env.play(&white, game, "f1c4").await.unwrap();
+    game
+}
+
+fn clocked_config() -> GameConfig {
+    GameConfig {
+        timer: 60,
+        inactivity_threshold: Some(60),
+        ..blitz_config(Some(WAGER))
+    }
+}
+
+#[tokio::test]
+async fn the_timer_settles_a_flagged_game() {
+    let game_config = clocked_config();
+    let mut env = TestEnv::new(3, game_config.get_time_category()).await;
+    let (white, black, cranker) = (env.player(0), env.player(1), env.player(2));
+    let game = start_clocked_game(&mut env, game_config).await;
+    let time_control = env.get_game(game).await.time_control;
+    assert_eq!(time_control.white_timer, 30);
+    assert_eq!(time_control.black_timer, 60);
+
+    // nothing to settle while black still has time
+    env.check_timer(&cranker, game).await.unwrap();
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+
+    env.warp(61).await;
+    assert_program_error(
+        env.play(&black, game, "g8f6").await,
+        CustomError::TimeHasRunOut,
+    );
+
+    env.check_timer(&cranker, game).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::WhiteWon);
+    let category = game_config.get_time_category();
+    let white_user = env.get_user(white.pubkey()).await;
+    let black_user = env.get_user(black.pubkey()).await;
+    assert_eq!(white_user.balance, 2 * WAGER);
+    assert_eq!(black_user.balance, 0);
+    assert_eq!(white_user.stats.wins, 1);
+    assert_eq!(black_user.stats.timeouts, 1);
+    assert!(white_user.get_active_games().is_empty());
+    assert!(black_user.get_active_games().is_empty());
+    assert!(white_user.get_elo(category) > DEFAULT_RATING as u32);
+    assert!(black_user.get_elo(category) < DEFAULT_RATING as u32);
+
+    assert_program_error(
+        env.check_timer(&cranker, game).await,
+        CustomError::InvalidGameState,
+    );
+}
+
+#[tokio::test]
+async fn the_timer_only_takes_the_games_players() {
+    let game_config = clocked_config();
+    let mut env = TestEnv::new(3, game_config.get_time_category()).await;
+    let (white, black, cranker) = (env.player(0), env.player(1), env.player(2));
+    let game = start_clocked_game(&mut env, game_config).await;
+    env.warp(61).await;
+    let game_account = env.get_game(game).await;
+
+    let outsider = get_user_key(cranker.pubkey());
+    for (user, adversary_user) in [
+        (get_user_key(white.pubkey()), get_user_key(black.pubkey())),
+        (outsider, get_user_key(white.pubkey())),
+        (get_user_key(black.pubkey()), outsider),
+    ] {
+        let check_timer = with_leaderboard(
+            build(
+                accounts::CheckTimer {
+                    payer: cranker.pubkey(),
+                    user,
+                    adversary_user,
+                    game,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::CheckTimer {},
+            ),
+            &game_account,
+        );
+        assert!(env.process(&cranker, check_timer).await.is_err());
+    }
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+}
+
+#[tokio::test]
+async fn a_flagged_player_can_be_claimed_abandoned() {
+    let game_config = clocked_config();
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_clocked_game(&mut env, game_config).await;
+    env.warp(61).await;
+
+    env.claim_abandonment(&white, game, true).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::WhiteWon);
+    let category = game_config.get_time_category();
+    let white_user = env.get_user(white.pubkey()).await;
+    let black_user = env.get_user(black.pubkey()).await;
+    assert_eq!(white_user.balance, 2 * WAGER);
+    assert_eq!(black_user.balance, 0);
+    assert_eq!(black_user.stats.abandonments, 1);
+    // too short a game to be rated
+    assert_eq!(white_user.get_elo(category), DEFAULT_RATING as u32);
+    assert_eq!(black_user.get_elo(category), DEFAULT_RATING as u32);
+}
+
+#[tokio::test]
+async fn a_no_contest_claim_refunds_both_players() {
+    let game_config = clocked_config();
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_clocked_game(&mut env, game_config).await;
+    env.warp(61).await;
+
+    env.claim_abandonment(&white, game, false).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::Aborted);
+    for player in [&white, &black] {
+        let user = env.get_user(player.pubkey()).await;
+        assert_eq!(user.balance, WAGER);
+        assert!(user.get_active_games().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn a_short_game_can_only_be_claimed_as_no_contest() {
+    let game_config = clocked_config();
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+    for (ply, mv) in ["e2e4", "e7e5", "g1f3"].iter().enumerate() {
+        let player = if ply % 2 == 0 { &white } else { &black };
+        env.play(player, game, mv).await.unwrap();
+    }
+    env.warp(61).await;
+
+    assert_program_error(
+        env.claim_abandonment(&white, game, true).await,
+        CustomError::TooFewMovesToClaimWin,
+    );
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+    for player in [&white, &black] {
+        assert_eq!(
+            env.get_user(player.pubkey()).await.get_active_games(),
+            vec![game]
+        );
+    }
+
+    env.claim_abandonment(&white, game, false).await.unwrap();
+    assert_eq!(env.get_game(game).await.game_state, GameState::Aborted);
+}
@@ -0,0 +1,702 @@
+//! Harness for the integration tests: the program runs natively inside a
+//! `BanksClient` bank, with funded players and a prefunded vault.
+
+#![allow(dead_code)]
+
+use {
+    anchor_lang::{
+        prelude::*, solana_program::sysvar, system_program, AccountDeserialize, InstructionData,
+    },
+    sol_chess::{
+        accounts, instruction, CustomError, Game, GameConfig, Invite, Leaderboard, LobbyPage,
+        MatchmakingQueue, MoveLog, Square, TimeCategory, User,
+    },
+    solana_program_test::{
+        processor, BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext,
+    },
+    solana_sdk::{
+        account::Account,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+pub const PLAYER_LAMPORTS: u64 = 100_000_000_000;
+pub const WAGER: u64 = 1_000_000_000;
+// keeps the vault rent exempt before the first deposit
+pub const VAULT_LAMPORTS: u64 = 1_000_000;
+
+pub type TestResult = std::result::Result<(), BanksClientError>;
+
+pub fn get_vault() -> Pubkey {
+    Pubkey::find_program_address(&[b"vault"], &sol_chess::ID).0
+}
+
+pub fn get_user_key(authority: Pubkey) -> Pubkey {
+    User::pda(authority).0
+}
+
+pub fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: sol_chess::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Appends the leaderboard of the game's time category for rated games, the
+/// way the SDK does.
+pub fn with_leaderboard(mut instruction: Instruction, game_account: &Game) -> Instruction {
+    if game_account.is_rated() {
+        instruction.accounts.push(AccountMeta::new(
+            Leaderboard::pda(game_account.get_time_category()).0,
+            false,
+        ));
+    }
+    instruction
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub players: Vec<Keypair>,
+    // every transaction waits for a new blockhash so that repeating one does
+    // not get it dropped as already processed
+    last_blockhash: Hash,
+}
+
+impl TestEnv {
+    /// Starts a bank with `players` funded wallets, each with a user account,
+    /// the first lobby page and the leaderboard of `category`.
+    pub async fn new(players: usize, category: TimeCategory) -> Self {
+        let mut program_test =
+            ProgramTest::new("sol_chess", sol_chess::ID, processor!(sol_chess::entry));
+        let players: Vec<Keypair> = (0..players).map(|_| Keypair::new()).collect();
+        for player in &players {
+            program_test.add_account(
+                player.pubkey(),
+                Account::new(PLAYER_LAMPORTS, 0, &system_program::ID),
+            );
+        }
+        program_test.add_account(
+            get_vault(),
+            Account::new(VAULT_LAMPORTS, 0, &system_program::ID),
+        );
+
+        let context = program_test.start_with_context().await;
+        let mut env = Self {
+            last_blockhash: context.last_blockhash,
+            context,
+            players,
+        };
+        let payer = env.player(0);
+        env.initialize_lobby_page(&payer, 0).await.unwrap();
+        let leaderboard = build(
+            accounts::InitializeLeaderboard {
+                payer: payer.pubkey(),
+                leaderboard: Leaderboard::pda(category).0,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeLeaderboard { category },
+        );
+        env.process(&payer, leaderboard).await.unwrap();
+
+        for index in 0..env.players.len() {
+            let player = env.player(index);
+            let user = build(
+                accounts::InitializeUser {
+                    payer: player.pubkey(),
+                    user: get_user_key(player.pubkey()),
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeUser {},
+            );
+            env.process(&player, user).await.unwrap();
+        }
+        env
+    }
+
+    pub fn player(&self, index: usize) -> Keypair {
+        Keypair::from_bytes(&self.players[index].to_bytes()).unwrap()
+    }
+
+    pub async fn process(&mut self, signer: &Keypair, instruction: Instruction) -> TestResult {
+        self.last_blockhash = self
+            .context
+            .banks_client
+            .get_new_latest_blockhash(&self.last_blockhash)
+            .await
+            .unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.last_blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Compute units `instruction` consumes, simulated without landing it.
+    pub async fn compute_units(&mut self, signer: &Keypair, instruction: Instruction) -> u64 {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.last_blockhash,
+        );
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        simulation.result.unwrap().unwrap();
+        simulation.simulation_details.unwrap().units_consumed
+    }
+
+    pub async fn get_lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn get_user(&mut self, authority: Pubkey) -> User {
+        self.get_account(get_user_key(authority)).await
+    }
+
+    pub async fn get_game(&mut self, game: Pubkey) -> Game {
+        self.get_account(game).await
+    }
+
+    /// Moves the bank clock forward by `seconds`.
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn deposit(&mut self, player: &Keypair, amount: u64) {
+        let deposit = build(
+            accounts::Deposit {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                vault: get_vault(),
+                system_program: system_program::ID,
+            },
+            instruction::Deposit { amount },
+        );
+        self.process(player, deposit).await.unwrap();
+    }
+
+    pub async fn withdraw(&mut self, player: &Keypair, amount: u64) -> TestResult {
+        let withdraw = build(
+            accounts::Withdraw {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                vault: get_vault(),
+                system_program: system_program::ID,
+            },
+            instruction::Withdraw { amount },
+        );
+        self.process(player, withdraw).await
+    }
+
+    /// Lists a game on the first lobby page, returning its address.
+    pub async fn create_game(&mut self, creator: &Keypair, game_config: GameConfig) -> Pubkey {
+        let (game, result) = self.try_create_game(creator, game_config).await;
+        result.unwrap();
+        game
+    }
+
+    pub async fn try_create_game(
+        &mut self,
+        creator: &Keypair,
+        game_config: GameConfig,
+    ) -> (Pubkey, TestResult) {
+        self.initialize_game(creator, game_config, 0, None).await
+    }
+
+    /// Creates a game reachable only through `invite`, returning its address.
+    pub async fn create_private_game(
+        &mut self,
+        creator: &Keypair,
+        game_config: GameConfig,
+        invite: Invite,
+    ) -> Pubkey {
+        let (game, result) = self
+            .initialize_game(creator, game_config, 0, Some(invite))
+            .await;
+        result.unwrap();
+        game
+    }
+
+    pub async fn initialize_game(
+        &mut self,
+        creator: &Keypair,
+        game_config: GameConfig,
+        page: u32,
+        invite: Option<Invite>,
+    ) -> (Pubkey, TestResult) {
+        let user = get_user_key(creator.pubkey());
+        let game = Game::pda(user, self.get_user(creator.pubkey()).await.games_created).0;
+        let create = build(
+            accounts::InitializeGame {
+                payer: creator.pubkey(),
+                user,
+                game,
+                move_log: MoveLog::pda(game).0,
+                lobby_page: LobbyPage::pda(page).0,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            },
+            instruction::InitializeGame {
+                game_config,
+                rating_range: None,
+                invite,
+            },
+        );
+        (game, self.process(creator, create).await)
+    }
+
+    pub async fn join_game(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        self.join_private_game(player, game, None).await
+    }
+
+    pub async fn join_private_game(
+        &mut self,
+        player: &Keypair,
+        game: Pubkey,
+        invite_code: Option<&str>,
+    ) -> TestResult {
+        let page = self.get_game(game).await.lobby_page;
+        let join = build(
+            accounts::JoinGame {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+                lobby_page: LobbyPage::pda(page).0,
+                recent_slothashes: sysvar::slot_hashes::ID,
+                clock: sysvar::clock::ID,
+            },
+            instruction::JoinGame {
+                invite_code: invite_code.map(String::from),
+            },
+        );
+        self.process(player, join).await
+    }
+
+    pub async fn leave_game(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let leave = build(
+            accounts::LeaveGame {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+                lobby_page: LobbyPage::pda(0).0,
+            },
+            instruction::LeaveGame {},
+        );
+        self.process(player, leave).await
+    }
+
+    pub async fn cancel_game(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let cancel = build(
+            accounts::CancelGame {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+                move_log: MoveLog::pda(game).0,
+                lobby_page: LobbyPage::pda(0).0,
+            },
+            instruction::CancelGame {},
+        );
+        self.process(player, cancel).await
+    }
+
+    // the user accounts of `player` and of their adversary in `game`, an empty
+    // seat is filled with the user for the program to reject
+    async fn get_players(&mut self, player: &Keypair, game: Pubkey) -> (Pubkey, Pubkey) {
+        let game_account = self.get_game(game).await;
+        let user = get_user_key(player.pubkey());
+        let adversary_user = if game_account.white == Some(user) {
+            game_account.black
+        } else {
+            game_account.white
+        };
+        (user, adversary_user.unwrap_or(user))
+    }
+
+    /// Plays `mv`, in coordinate notation such as `e2e4`.
+    pub async fn play(&mut self, player: &Keypair, game: Pubkey, mv: &str) -> TestResult {
+        let from = Square::from_algebraic(&mv[..2]).unwrap();
+        let to = Square::from_algebraic(&mv[2..]).unwrap();
+        self.move_piece(player, game, from, to).await
+    }
+
+    pub async fn move_piece(
+        &mut self,
+        player: &Keypair,
+        game: Pubkey,
+        from: Square,
+        to: Square,
+    ) -> TestResult {
+        let move_piece = self.move_piece_instruction(player, game, from, to).await;
+        self.process(player, move_piece).await
+    }
+
+    pub async fn move_piece_instruction(
+        &mut self,
+        player: &Keypair,
+        game: Pubkey,
+        from: Square,
+        to: Square,
+    ) -> Instruction {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let game_account = self.get_game(game).await;
+        with_leaderboard(
+            build(
+                accounts::MovePiece {
+                    payer: player.pubkey(),
+                    user,
+                    adversary_user,
+                    game,
+                    move_log: MoveLog::pda(game).0,
+                    system_program: system_program::ID,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::MovePiece { from, to },
+            ),
+            &game_account,
+        )
+    }
+
+    pub async fn resign(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let game_account = self.get_game(game).await;
+        let resign = with_leaderboard(
+            build(
+                accounts::Resign {
+                    payer: player.pubkey(),
+                    user,
+                    adversary_user,
+                    game,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::Resign {},
+            ),
+            &game_account,
+        );
+        self.process(player, resign).await
+    }
+
+    pub async fn offer_draw(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let game_account = self.get_game(game).await;
+        let offer_draw = with_leaderboard(
+            build(
+                accounts::OfferDraw {
+                    payer: player.pubkey(),
+                    user,
+                    adversary_user,
+                    game,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::OfferDraw {},
+            ),
+            &game_account,
+        );
+        self.process(player, offer_draw).await
+    }
+
+    pub async fn claim_abandonment(
+        &mut self,
+        player: &Keypair,
+        game: Pubkey,
+        claim_win: bool,
+    ) -> TestResult {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let game_account = self.get_game(game).await;
+        let claim = with_leaderboard(
+            build(
+                accounts::ClaimAbandonment {
+                    payer: player.pubkey(),
+                    user,
+                    adversary_user,
+                    game,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::ClaimAbandonment { claim_win },
+            ),
+            &game_account,
+        );
+        self.process(player, claim).await
+    }
+
+    /// Settles a flagged game the way the clock thread would, `payer` only
+    /// pays for the transaction.
+    pub async fn check_timer(&mut self, payer: &Keypair, game: Pubkey) -> TestResult {
+        let game_account = self.get_game(game).await;
+        let (user, adversary_user) = if game_account.game_state.is_white_turn() {
+            (game_account.white, game_account.black)
+        } else {
+            (game_account.black, game_account.white)
+        };
+        let check_timer = with_leaderboard(
+            build(
+                accounts::CheckTimer {
+                    payer: payer.pubkey(),
+                    user: user.unwrap(),
+                    adversary_user: adversary_user.unwrap(),
+                    game,
+                    clock: sysvar::clock::ID,
+                },
+                instruction::CheckTimer {},
+            ),
+            &game_account,
+        );
+        self.process(payer, check_timer).await
+    }
+
+    pub async fn initialize_lobby_page(&mut self, payer: &Keypair, page: u32) -> TestResult {
+        let lobby_page = build(
+            accounts::InitializeLobbyPage {
+                payer: payer.pubkey(),
+                lobby_page: LobbyPage::pda(page).0,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeLobbyPage { page },
+        );
+        self.process(payer, lobby_page).await
+    }
+
+    pub async fn get_lobby_page(&mut self, page: u32) -> LobbyPage {
+        self.get_account(LobbyPage::pda(page).0).await
+    }
+
+    pub async fn set_max_active_games(
+        &mut self,
+        player: &Keypair,
+        max_active_games: u8,
+    ) -> TestResult {
+        let set_max_active_games = build(
+            accounts::SetMaxActiveGames {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+            },
+            instruction::SetMaxActiveGames { max_active_games },
+        );
+        self.process(player, set_max_active_games).await
+    }
+
+    pub async fn abort(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let abort = build(
+            accounts::Abort {
+                payer: player.pubkey(),
+                user,
+                adversary_user,
+                game,
+                clock: sysvar::clock::ID,
+            },
+            instruction::Abort {},
+        );
+        self.process(player, abort).await
+    }
+
+    pub async fn decline_draw(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let decline_draw = build(
+            accounts::DeclineDraw {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+            },
+            instruction::DeclineDraw {},
+        );
+        self.process(player, decline_draw).await
+    }
+
+    pub async fn request_takeback(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let request_takeback = build(
+            accounts::RequestTakeback {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+            },
+            instruction::RequestTakeback {},
+        );
+        self.process(player, request_takeback).await
+    }
+
+    pub async fn accept_takeback(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let accept_takeback = build(
+            accounts::AcceptTakeback {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+                move_log: MoveLog::pda(game).0,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            },
+            instruction::AcceptTakeback {},
+        );
+        self.process(player, accept_takeback).await
+    }
+
+    pub async fn decline_takeback(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let decline_takeback = build(
+            accounts::DeclineTakeback {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+            },
+            instruction::DeclineTakeback {},
+        );
+        self.process(player, decline_takeback).await
+    }
+
+    pub async fn offer_rematch(&mut self, player: &Keypair, game: Pubkey) -> TestResult {
+        let offer_rematch = build(
+            accounts::OfferRematch {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                game,
+            },
+            instruction::OfferRematch {},
+        );
+        self.process(player, offer_rematch).await
+    }
+
+    /// Accepts the rematch of `game`, returning the address of the new game.
+    pub async fn accept_rematch(&mut self, player: &Keypair, game: Pubkey) -> (Pubkey, TestResult) {
+        let (user, adversary_user) = self.get_players(player, game).await;
+        let rematch_game = Game::pda(user, self.get_user(player.pubkey()).await.games_created).0;
+        let accept_rematch = build(
+            accounts::AcceptRematch {
+                payer: player.pubkey(),
+                user,
+                adversary_user,
+                game,
+                rematch_game,
+                move_log: MoveLog::pda(rematch_game).0,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            },
+            instruction::AcceptRematch {},
+        );
+        (rematch_game, self.process(player, accept_rematch).await)
+    }
+
+    pub async fn initialize_matchmaking_queue(
+        &mut self,
+        payer: &Keypair,
+        game_config: GameConfig,
+    ) -> TestResult {
+        let initialize_queue = build(
+            accounts::InitializeMatchmakingQueue {
+                payer: payer.pubkey(),
+                matchmaking_queue: MatchmakingQueue::pda(&game_config).0,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeMatchmakingQueue { game_config },
+        );
+        self.process(payer, initialize_queue).await
+    }
+
+    pub async fn get_matchmaking_queue(&mut self, game_config: &GameConfig) -> MatchmakingQueue {
+        self.get_account(MatchmakingQueue::pda(game_config).0).await
+    }
+
+    pub async fn enqueue(&mut self, player: &Keypair, game_config: &GameConfig) -> TestResult {
+        let enqueue = build(
+            accounts::Enqueue {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                matchmaking_queue: MatchmakingQueue::pda(game_config).0,
+                clock: sysvar::clock::ID,
+            },
+            instruction::Enqueue {},
+        );
+        self.process(player, enqueue).await
+    }
+
+    pub async fn dequeue(&mut self, player: &Keypair, game_config: &GameConfig) -> TestResult {
+        let dequeue = build(
+            accounts::Dequeue {
+                payer: player.pubkey(),
+                user: get_user_key(player.pubkey()),
+                matchmaking_queue: MatchmakingQueue::pda(game_config).0,
+            },
+            instruction::Dequeue {},
+        );
+        self.process(player, dequeue).await
+    }
+
+    /// Pairs two queued players, `payer` only pays for the game accounts.
+    /// Returns the address of the new game.
+    pub async fn match_players(
+        &mut self,
+        payer: &Keypair,
+        game_config: &GameConfig,
+        player: &Keypair,
+        adversary: &Keypair,
+    ) -> (Pubkey, TestResult) {
+        let user = get_user_key(player.pubkey());
+        let game = Game::pda(user, self.get_user(player.pubkey()).await.games_created).0;
+        let match_players = build(
+            accounts::MatchPlayers {
+                payer: payer.pubkey(),
+                user,
+                adversary_user: get_user_key(adversary.pubkey()),
+                matchmaking_queue: MatchmakingQueue::pda(game_config).0,
+                game,
+                move_log: MoveLog::pda(game).0,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            },
+            instruction::MatchPlayers {},
+        );
+        (game, self.process(payer, match_players).await)
+    }
+}
+
+/// A rated blitz game with the creator as white.
+pub fn blitz_config(wager: Option<u64>) -> GameConfig {
+    GameConfig {
+        timer: 300,
+        increment: 0,
+        is_rated: true,
+        wager,
+        color: sol_chess::ColorPreference::White,
+        allow_takebacks: false,
+        inactivity_threshold: None,
+    }
+}
+
+pub fn assert_program_error(result: TestResult, expected: CustomError) {
+    let error = match result.map_err(|err| err.unwrap()) {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+            CustomError::from_code(code)
+        }
+        _ => None,
+    };
+    match error {
+        Some(error) => assert_eq!(error as u32, expected as u32, "{}", error),
+        None => panic!("expected {}", expected),
+    }
+}
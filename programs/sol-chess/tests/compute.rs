@@ -0,0 +1,33 @@
+mod common;
+
+use {common::*, sol_chess::Square};
+
+// the default budget of a single instruction
+const COMPUTE_BUDGET: u64 = 200_000;
+
+// compute units are only meaningful for the BPF build, the native processor
+// the rest of the suite runs does not meter them:
+// `cargo test-bpf -p sol-chess --test compute -- --ignored`
+#[tokio::test]
+#[ignore = "needs the BPF build"]
+async fn a_rated_checkmate_fits_the_compute_budget() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+    for (ply, mv) in ["f2f3", "e7e5", "g2g4"].iter().enumerate() {
+        let player = if ply % 2 == 0 { &white } else { &black };
+        env.play(player, game, mv).await.unwrap();
+    }
+
+    // settles the game, rating both players and updating the leaderboard
+    let (from, to) = (
+        Square::from_algebraic("d8").unwrap(),
+        Square::from_algebraic("h4").unwrap(),
+    );
+    let mate = env.move_piece_instruction(&black, game, from, to).await;
+    let units = env.compute_units(&black, mate).await;
+    println!("checkmate settlement: {} compute units", units);
+    assert!(units < COMPUTE_BUDGET);
+}
@@ -0,0 +1,414 @@
+mod common;
+
+use {
+    anchor_lang::prelude::Pubkey,
+    common::*,
+    sol_chess::{
+        accounts, instruction, CustomError, GameConfig, GameState, Leaderboard, LoggedMove,
+        MoveLog, Piece, Square, TakebackState, TimeCategory, DEFAULT_RATING,
+    },
+    solana_sdk::{signature::Signer, sysvar},
+};
+
+const FOOLS_MATE: [&str; 4] = ["f2f3", "e7e5", "g2g4", "d8h4"];
+
+// a started game between the first two players, both having escrowed `WAGER`
+async fn start_game(env: &mut TestEnv, game_config: GameConfig) -> Pubkey {
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+    game
+}
+
+fn square(algebraic: &str) -> Square {
+    Square::from_algebraic(algebraic).unwrap()
+}
+
+#[tokio::test]
+async fn create_and_join_escrow_the_wagers() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+
+    let game = env.create_game(&white, game_config).await;
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.game_state, GameState::Waiting);
+    assert_eq!(game_account.white, Some(get_user_key(white.pubkey())));
+    assert_eq!(env.get_user(white.pubkey()).await.balance, 0);
+
+    env.join_game(&black, game).await.unwrap();
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.game_state, GameState::White);
+    assert_eq!(game_account.black, Some(get_user_key(black.pubkey())));
+    assert_eq!(env.get_user(black.pubkey()).await.balance, 0);
+
+    assert_program_error(
+        env.join_game(&black, game).await,
+        CustomError::ColorNotAvailable,
+    );
+}
+
+#[tokio::test]
+async fn cancelling_refunds_and_closes_the_game_and_move_log() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(1, game_config.get_time_category()).await;
+    let creator = env.player(0);
+    env.deposit(&creator, WAGER).await;
+    let game = env.create_game(&creator, game_config).await;
+
+    env.cancel_game(&creator, game).await.unwrap();
+
+    assert_eq!(env.get_user(creator.pubkey()).await.balance, WAGER);
+    assert_eq!(env.get_lamports(game).await, 0);
+    assert_eq!(env.get_lamports(MoveLog::pda(game).0).await, 0);
+}
+
+#[tokio::test]
+async fn illegal_moves_are_rejected() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+
+    assert_program_error(
+        env.play(&black, game, "e7e5").await,
+        CustomError::NotUsersTurn,
+    );
+    assert_program_error(
+        env.play(&white, game, "e2e5").await,
+        CustomError::InvalidMove,
+    );
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.ply, 1);
+    assert_eq!(game_account.game_state, GameState::Black);
+}
+
+#[tokio::test]
+async fn checkmate_pays_the_winner_and_updates_ratings() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+
+    for (ply, mv) in FOOLS_MATE.iter().enumerate() {
+        let player = if ply % 2 == 0 { &white } else { &black };
+        env.play(player, game, mv).await.unwrap();
+    }
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::BlackWon);
+    let category = game_config.get_time_category();
+    let white_user = env.get_user(white.pubkey()).await;
+    let black_user = env.get_user(black.pubkey()).await;
+    assert_eq!(white_user.balance, 0);
+    assert_eq!(black_user.balance, 2 * WAGER);
+    assert!(white_user.get_elo(category) < DEFAULT_RATING as u32);
+    assert!(black_user.get_elo(category) > DEFAULT_RATING as u32);
+    assert_eq!(white_user.stats.losses, 1);
+    assert_eq!(black_user.stats.wins, 1);
+    assert!(white_user.get_active_games().is_empty());
+
+    let leaderboard: Leaderboard = env.get_account(Leaderboard::pda(category).0).await;
+    assert_eq!(leaderboard.get_rank(get_user_key(black.pubkey())), Some(0));
+    assert_eq!(leaderboard.get_rank(get_user_key(white.pubkey())), Some(1));
+}
+
+#[tokio::test]
+async fn resigning_pays_the_adversary() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    env.resign(&white, game).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::BlackWon);
+    let category = game_config.get_time_category();
+    let white_user = env.get_user(white.pubkey()).await;
+    let black_user = env.get_user(black.pubkey()).await;
+    assert_eq!(white_user.balance, 0);
+    assert_eq!(black_user.balance, 2 * WAGER);
+    assert_eq!(white_user.stats.resignations, 1);
+    assert!(white_user.get_elo(category) < black_user.get_elo(category));
+
+    assert_program_error(
+        env.resign(&black, game).await,
+        CustomError::InvalidGameState,
+    );
+}
+
+#[tokio::test]
+async fn unrated_games_settle_without_a_leaderboard() {
+    let game_config = GameConfig {
+        timer: 60,
+        is_rated: false,
+        ..blitz_config(Some(WAGER))
+    };
+    assert_eq!(game_config.get_time_category(), TimeCategory::Bullet);
+    // only the blitz leaderboard exists
+    let mut env = TestEnv::new(2, TimeCategory::Blitz).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    env.resign(&white, game).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::BlackWon);
+    assert_eq!(env.get_user(black.pubkey()).await.balance, 2 * WAGER);
+}
+
+#[tokio::test]
+async fn rated_games_need_the_leaderboard_to_settle() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    let resign = build(
+        accounts::Resign {
+            payer: white.pubkey(),
+            user: get_user_key(white.pubkey()),
+            adversary_user: get_user_key(black.pubkey()),
+            game,
+            clock: sysvar::clock::ID,
+        },
+        instruction::Resign {},
+    );
+    assert_program_error(
+        env.process(&white, resign).await,
+        CustomError::InvalidLeaderboardAccount,
+    );
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+
+    env.resign(&white, game).await.unwrap();
+    let leaderboard: Leaderboard = env
+        .get_account(Leaderboard::pda(game_config.get_time_category()).0)
+        .await;
+    assert_eq!(leaderboard.get_rank(get_user_key(black.pubkey())), Some(0));
+}
+
+#[tokio::test]
+async fn mutual_draw_offers_refund_both_players() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    env.deposit(&white, WAGER).await;
+    env.deposit(&black, WAGER).await;
+    let game = env.create_game(&white, game_config).await;
+    env.join_game(&black, game).await.unwrap();
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    env.offer_draw(&black, game).await.unwrap();
+    assert_program_error(
+        env.offer_draw(&black, game).await,
+        CustomError::AlreadyOfferedDraw,
+    );
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+
+    env.offer_draw(&white, game).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::Draw);
+    let category = game_config.get_time_category();
+    for player in [&white, &black] {
+        let user = env.get_user(player.pubkey()).await;
+        assert_eq!(user.balance, WAGER);
+        assert_eq!(user.stats.draws, 1);
+        assert_eq!(user.get_elo(category), DEFAULT_RATING as u32);
+    }
+}
+
+#[tokio::test]
+async fn aborting_before_both_first_moves_refunds_both_players() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    env.abort(&black, game).await.unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::Aborted);
+    for player in [&white, &black] {
+        let user = env.get_user(player.pubkey()).await;
+        assert_eq!(user.balance, WAGER);
+        assert!(user.get_active_games().is_empty());
+        assert_eq!(user.stats.losses, 0);
+    }
+}
+
+#[tokio::test]
+async fn aborting_after_both_first_moves_fails() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+    env.play(&black, game, "e7e5").await.unwrap();
+
+    assert_program_error(env.abort(&white, game).await, CustomError::CannotAbort);
+    assert_eq!(env.get_game(game).await.game_state, GameState::White);
+}
+
+#[tokio::test]
+async fn a_declined_draw_offer_keeps_the_game_going() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    assert_program_error(
+        env.decline_draw(&white, game).await,
+        CustomError::NoDrawOffer,
+    );
+    env.offer_draw(&black, game).await.unwrap();
+    // only the player the offer was made to can decline it
+    assert_program_error(
+        env.decline_draw(&black, game).await,
+        CustomError::NoDrawOffer,
+    );
+    env.decline_draw(&white, game).await.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.game_state, GameState::Black);
+    assert_eq!(game_account.draw_state.get_offer(), None);
+    // a declined offer is not accepted by a later offer from the other side
+    env.offer_draw(&white, game).await.unwrap();
+    assert_eq!(env.get_game(game).await.game_state, GameState::Black);
+}
+
+#[tokio::test]
+async fn an_accepted_takeback_restores_the_position() {
+    let game_config = GameConfig {
+        allow_takebacks: true,
+        ..blitz_config(Some(WAGER))
+    };
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+    env.play(&black, game, "d7d5").await.unwrap();
+    env.play(&white, game, "e4d5").await.unwrap();
+
+    // white asks to take back its capture before black has answered it
+    env.request_takeback(&white, game).await.unwrap();
+    assert_program_error(
+        env.request_takeback(&white, game).await,
+        CustomError::AlreadyRequestedTakeback,
+    );
+    assert_program_error(
+        env.accept_takeback(&white, game).await,
+        CustomError::NoTakebackRequested,
+    );
+    env.accept_takeback(&black, game).await.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.ply, 2);
+    assert_eq!(game_account.game_state, GameState::White);
+    assert_eq!(game_account.board.get_piece(square("e4")), Piece::WhitePawn);
+    assert_eq!(game_account.board.get_piece(square("d5")), Piece::BlackPawn);
+    let move_log: MoveLog = env.get_account(MoveLog::pda(game).0).await;
+    assert!(matches!(
+        move_log.decode().unwrap().last(),
+        Some(LoggedMove::Takeback { plies: 1, .. })
+    ));
+
+    env.play(&white, game, "g1f3").await.unwrap();
+}
+
+#[tokio::test]
+async fn a_declined_takeback_keeps_the_position() {
+    let game_config = GameConfig {
+        allow_takebacks: true,
+        ..blitz_config(None)
+    };
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    env.request_takeback(&white, game).await.unwrap();
+    env.decline_takeback(&black, game).await.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.ply, 1);
+    assert_eq!(game_account.takeback_state, TakebackState::Neither);
+    assert_program_error(
+        env.accept_takeback(&black, game).await,
+        CustomError::NoTakebackRequested,
+    );
+}
+
+#[tokio::test]
+async fn takebacks_need_the_game_to_allow_them() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let white = env.player(0);
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+
+    assert_program_error(
+        env.request_takeback(&white, game).await,
+        CustomError::TakebacksNotAllowed,
+    );
+}
+
+#[tokio::test]
+async fn a_rematch_swaps_colors_and_escrows_the_wagers() {
+    let game_config = blitz_config(Some(WAGER));
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (white, black) = (env.player(0), env.player(1));
+    let game = start_game(&mut env, game_config).await;
+    env.play(&white, game, "e2e4").await.unwrap();
+    assert_program_error(
+        env.offer_rematch(&white, game).await,
+        CustomError::GameNotFinished,
+    );
+    env.resign(&white, game).await.unwrap();
+
+    assert_program_error(
+        env.accept_rematch(&black, game).await.1,
+        CustomError::NoRematchOffer,
+    );
+    env.offer_rematch(&white, game).await.unwrap();
+    assert_program_error(
+        env.accept_rematch(&black, game).await.1,
+        CustomError::InsufficientBalance,
+    );
+    env.deposit(&white, WAGER).await;
+    let (rematch_game, result) = env.accept_rematch(&black, game).await;
+    result.unwrap();
+
+    let rematch = env.get_game(rematch_game).await;
+    assert_eq!(rematch.game_state, GameState::White);
+    assert_eq!(rematch.white, Some(get_user_key(black.pubkey())));
+    assert_eq!(rematch.black, Some(get_user_key(white.pubkey())));
+    assert_eq!(rematch.game_config, game_config);
+    assert_eq!(env.get_user(black.pubkey()).await.balance, WAGER);
+    assert_eq!(env.get_user(white.pubkey()).await.balance, 0);
+    for player in [&white, &black] {
+        assert_eq!(
+            env.get_user(player.pubkey()).await.get_active_games(),
+            vec![rematch_game]
+        );
+    }
+
+    assert_program_error(
+        env.offer_rematch(&white, game).await,
+        CustomError::AlreadyOfferedRematch,
+    );
+}
@@ -0,0 +1,272 @@
+//! Random instruction sequences through the program, checking that no path
+//! panics and that the vault always covers balances and escrowed wagers.
+
+mod common;
+
+use {
+    anchor_lang::prelude::Pubkey,
+    common::*,
+    proptest::prelude::*,
+    sol_chess::{GameConfig, Square},
+    solana_sdk::{instruction::InstructionError, signature::Signer, transaction::TransactionError},
+};
+
+const PLAYERS: usize = 3;
+
+#[derive(Clone, Debug)]
+enum Action {
+    Deposit {
+        player: usize,
+        amount: u64,
+    },
+    Withdraw {
+        player: usize,
+        amount: u64,
+    },
+    CreateGame {
+        player: usize,
+        wager: bool,
+    },
+    JoinGame {
+        player: usize,
+        game: usize,
+    },
+    LeaveGame {
+        player: usize,
+        game: usize,
+    },
+    // a legal move picked by `choice`, or any pair of squares
+    PlayLegal {
+        player: usize,
+        game: usize,
+        choice: usize,
+    },
+    Play {
+        player: usize,
+        game: usize,
+        from: Square,
+        to: Square,
+    },
+    Resign {
+        player: usize,
+        game: usize,
+    },
+    OfferDraw {
+        player: usize,
+        game: usize,
+    },
+    ClaimAbandonment {
+        player: usize,
+        game: usize,
+        claim_win: bool,
+    },
+    Warp {
+        seconds: i64,
+    },
+}
+
+// off the board squares included, the program is handed whatever clients send
+fn square() -> impl Strategy<Value = Square> {
+    (0..10u8, 0..10u8).prop_map(|(rank, file)| Square { rank, file })
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let player = 0..PLAYERS;
+    let game = any::<usize>();
+    let amount = 0..3 * WAGER;
+    prop_oneof![
+        1 => (player.clone(), amount.clone())
+            .prop_map(|(player, amount)| Action::Deposit { player, amount }),
+        1 => (player.clone(), amount)
+            .prop_map(|(player, amount)| Action::Withdraw { player, amount }),
+        1 => (player.clone(), any::<bool>())
+            .prop_map(|(player, wager)| Action::CreateGame { player, wager }),
+        2 => (player.clone(), game)
+            .prop_map(|(player, game)| Action::JoinGame { player, game }),
+        1 => (player.clone(), game)
+            .prop_map(|(player, game)| Action::LeaveGame { player, game }),
+        6 => (player.clone(), game, any::<usize>())
+            .prop_map(|(player, game, choice)| Action::PlayLegal { player, game, choice }),
+        1 => (player.clone(), game, square(), square())
+            .prop_map(|(player, game, from, to)| Action::Play { player, game, from, to }),
+        1 => (player.clone(), game)
+            .prop_map(|(player, game)| Action::Resign { player, game }),
+        1 => (player.clone(), game)
+            .prop_map(|(player, game)| Action::OfferDraw { player, game }),
+        1 => (player, game, any::<bool>()).prop_map(|(player, game, claim_win)| {
+            Action::ClaimAbandonment { player, game, claim_win }
+        }),
+        1 => (0..120i64).prop_map(|seconds| Action::Warp { seconds }),
+    ]
+}
+
+fn game_config(wager: bool) -> GameConfig {
+    GameConfig {
+        timer: 60,
+        inactivity_threshold: Some(60),
+        ..blitz_config(if wager { Some(WAGER) } else { None })
+    }
+}
+
+// rejected instructions must fail with a program error, a panic surfaces as
+// another instruction error
+fn check_result(result: TestResult, action: &Action) -> std::result::Result<(), TestCaseError> {
+    match result.map_err(|err| err.unwrap()) {
+        Ok(()) | Err(TransactionError::InstructionError(_, InstructionError::Custom(_))) => Ok(()),
+        Err(err) => Err(TestCaseError::fail(format!(
+            "{:?} failed with {}",
+            action, err
+        ))),
+    }
+}
+
+struct Fuzzer {
+    env: TestEnv,
+    games: Vec<Pubkey>,
+}
+
+impl Fuzzer {
+    fn get_game(&self, game: usize) -> Option<Pubkey> {
+        match self.games.len() {
+            0 => None,
+            len => Some(self.games[game % len]),
+        }
+    }
+
+    async fn apply(&mut self, action: &Action) -> TestResult {
+        let env = &mut self.env;
+        match *action {
+            Action::Deposit { player, amount } => {
+                env.deposit(&env.player(player), amount).await;
+                Ok(())
+            }
+            Action::Withdraw { player, amount } => env.withdraw(&env.player(player), amount).await,
+            Action::CreateGame { player, wager } => {
+                let (game, result) = env
+                    .try_create_game(&env.player(player), game_config(wager))
+                    .await;
+                if result.is_ok() {
+                    self.games.push(game);
+                }
+                result
+            }
+            Action::Warp { seconds } => {
+                env.warp(seconds).await;
+                Ok(())
+            }
+            _ => self.apply_to_game(action).await,
+        }
+    }
+
+    async fn apply_to_game(&mut self, action: &Action) -> TestResult {
+        let (player, game) = match *action {
+            Action::JoinGame { player, game }
+            | Action::LeaveGame { player, game }
+            | Action::PlayLegal { player, game, .. }
+            | Action::Play { player, game, .. }
+            | Action::Resign { player, game }
+            | Action::OfferDraw { player, game }
+            | Action::ClaimAbandonment { player, game, .. } => (player, game),
+            _ => unreachable!(),
+        };
+        let game = match self.get_game(game) {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let env = &mut self.env;
+        let player = env.player(player);
+        match *action {
+            Action::JoinGame { .. } => env.join_game(&player, game).await,
+            Action::LeaveGame { .. } => env.leave_game(&player, game).await,
+            Action::PlayLegal { choice, .. } => {
+                let game_account = env.get_game(game).await;
+                let color = match game_account.get_current_player_color() {
+                    Ok(color) => color,
+                    Err(_) => return Ok(()),
+                };
+                let legal_moves = game_account.get_legal_moves(color);
+                if legal_moves.is_empty() {
+                    return Ok(());
+                }
+                let (from, to) = legal_moves[choice % legal_moves.len()];
+                env.move_piece(&player, game, from, to).await
+            }
+            Action::Play { from, to, .. } => env.move_piece(&player, game, from, to).await,
+            Action::Resign { .. } => env.resign(&player, game).await,
+            Action::OfferDraw { .. } => env.offer_draw(&player, game).await,
+            Action::ClaimAbandonment { claim_win, .. } => {
+                env.claim_abandonment(&player, game, claim_win).await
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn check_invariants(&mut self) -> std::result::Result<(), TestCaseError> {
+        let mut owed = 0;
+        for index in 0..PLAYERS {
+            let authority = self.env.player(index).pubkey();
+            owed += self.env.get_user(authority).await.balance;
+        }
+
+        for game in self.games.clone() {
+            let game_account = self.env.get_game(game).await;
+            let is_escrowed = !game_account.is_finished();
+            if is_escrowed {
+                let seats = game_account.white.iter().chain(&game_account.black).count();
+                owed += game_account.get_wager_or_zero() * seats as u64;
+            }
+
+            // the player who just moved can never be left in check
+            if let Ok(color) = game_account.get_current_player_color() {
+                prop_assert!(game_account.not_in_check(color.get_opposite()).unwrap());
+            }
+
+            for (seat, color) in [(game_account.white, "white"), (game_account.black, "black")] {
+                if let Some(user) = seat {
+                    let user = self.env.get_account::<sol_chess::User>(user).await;
+                    prop_assert_eq!(
+                        user.get_active_games().contains(&game),
+                        is_escrowed,
+                        "{} seat of {}",
+                        color,
+                        game
+                    );
+                }
+            }
+        }
+
+        let vault = self.env.get_lamports(get_vault()).await;
+        prop_assert_eq!(vault, VAULT_LAMPORTS + owed);
+        Ok(())
+    }
+}
+
+async fn run(actions: Vec<Action>) -> std::result::Result<(), TestCaseError> {
+    let category = game_config(false).get_time_category();
+    let mut fuzzer = Fuzzer {
+        env: TestEnv::new(PLAYERS, category).await,
+        games: vec![],
+    };
+    for action in &actions {
+        let result = fuzzer.apply(action).await;
+        check_result(result, action)?;
+        fuzzer.check_invariants().await?;
+    }
+    Ok(())
+}
+
+proptest! {
+    // every case starts a new bank
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn random_instructions_keep_the_vault_balanced(
+        actions in prop::collection::vec(action(), 1..48),
+    ) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(run(actions))?;
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use {
+    common::*,
+    sol_chess::{Color, ColorPreference, CustomError, GameConfig, GameState, Invite},
+    solana_sdk::signature::Signer,
+};
+
+#[tokio::test]
+async fn open_games_are_listed_until_joined() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(3, game_config.get_time_category()).await;
+    let (creator, joiner, other) = (env.player(0), env.player(1), env.player(2));
+    let game = env.create_game(&creator, game_config).await;
+    let other_game = env.create_game(&other, game_config).await;
+
+    let lobby_page = env.get_lobby_page(0).await;
+    let seeks = lobby_page.get_seeks();
+    assert_eq!(seeks.len(), 2);
+    assert_eq!(seeks[0].game, game);
+    assert_eq!(seeks[0].creator, get_user_key(creator.pubkey()));
+    assert_eq!(seeks[0].color, Some(Color::Black));
+    assert_eq!(seeks[1].game, other_game);
+
+    env.join_game(&joiner, game).await.unwrap();
+    let lobby_page = env.get_lobby_page(0).await;
+    assert_eq!(lobby_page.get_seeks().len(), 1);
+    assert_eq!(lobby_page.get_seeks()[0].game, other_game);
+
+    env.leave_game(&other, other_game).await.unwrap();
+    assert!(env.get_lobby_page(0).await.get_seeks().is_empty());
+}
+
+#[tokio::test]
+async fn games_can_be_listed_on_any_page() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (creator, joiner) = (env.player(0), env.player(1));
+
+    let (_, result) = env.initialize_game(&creator, game_config, 1, None).await;
+    assert!(result.is_err());
+    env.initialize_lobby_page(&creator, 1).await.unwrap();
+    let (game, result) = env.initialize_game(&creator, game_config, 1, None).await;
+    result.unwrap();
+
+    assert_eq!(env.get_game(game).await.lobby_page, 1);
+    assert_eq!(env.get_lobby_page(1).await.get_seeks()[0].game, game);
+    assert!(env.get_lobby_page(0).await.get_seeks().is_empty());
+
+    env.join_game(&joiner, game).await.unwrap();
+    assert!(env.get_lobby_page(1).await.get_seeks().is_empty());
+}
+
+#[tokio::test]
+async fn random_colors_seat_both_players() {
+    let game_config = GameConfig {
+        color: ColorPreference::Random,
+        ..blitz_config(None)
+    };
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (creator, joiner) = (env.player(0), env.player(1));
+    let game = env.create_game(&creator, game_config).await;
+    assert_eq!(env.get_lobby_page(0).await.get_seeks()[0].color, None);
+
+    env.join_game(&joiner, game).await.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.game_state, GameState::White);
+    let mut players = [game_account.white.unwrap(), game_account.black.unwrap()];
+    players.sort();
+    let mut expected = [
+        get_user_key(creator.pubkey()),
+        get_user_key(joiner.pubkey()),
+    ];
+    expected.sort();
+    assert_eq!(players, expected);
+}
+
+#[tokio::test]
+async fn private_games_only_admit_the_invited_user() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(3, game_config.get_time_category()).await;
+    let (creator, invited, outsider) = (env.player(0), env.player(1), env.player(2));
+    let invite = Invite::User(get_user_key(invited.pubkey()));
+    let game = env.create_private_game(&creator, game_config, invite).await;
+
+    assert!(env.get_lobby_page(0).await.get_seeks().is_empty());
+    assert_program_error(
+        env.join_game(&outsider, game).await,
+        CustomError::NotInvited,
+    );
+    env.join_game(&invited, game).await.unwrap();
+
+    assert_eq!(
+        env.get_game(game).await.black,
+        Some(get_user_key(invited.pubkey()))
+    );
+}
+
+#[tokio::test]
+async fn private_games_admit_anyone_with_the_code() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (creator, joiner) = (env.player(0), env.player(1));
+    let invite = Invite::Code(Invite::hash_code("knight to f3"));
+    let game = env.create_private_game(&creator, game_config, invite).await;
+
+    assert_program_error(
+        env.join_private_game(&joiner, game, None).await,
+        CustomError::NotInvited,
+    );
+    assert_program_error(
+        env.join_private_game(&joiner, game, Some("knight to f6"))
+            .await,
+        CustomError::NotInvited,
+    );
+    env.join_private_game(&joiner, game, Some("knight to f3"))
+        .await
+        .unwrap();
+
+    assert_eq!(env.get_game(game).await.game_state, GameState::White);
+}
+
+#[tokio::test]
+async fn the_active_games_limit_is_enforced() {
+    let game_config = blitz_config(None);
+    let mut env = TestEnv::new(2, game_config.get_time_category()).await;
+    let (player, creator) = (env.player(0), env.player(1));
+
+    assert_program_error(
+        env.set_max_active_games(&player, 0).await,
+        CustomError::InvalidMaxActiveGames,
+    );
+    assert_program_error(
+        env.set_max_active_games(&player, 9).await,
+        CustomError::InvalidMaxActiveGames,
+    );
+    env.set_max_active_games(&player, 1).await.unwrap();
+    assert_eq!(env.get_user(player.pubkey()).await.max_active_games, 1);
+
+    env.create_game(&player, game_config).await;
+    let (_, result) = env.try_create_game(&player, game_config).await;
+    assert_program_error(result, CustomError::TooManyActiveGames);
+    let game = env.create_game(&creator, game_config).await;
+    assert_program_error(
+        env.join_game(&player, game).await,
+        CustomError::TooManyActiveGames,
+    );
+
+    env.set_max_active_games(&player, 2).await.unwrap();
+    env.join_game(&player, game).await.unwrap();
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use {
+    common::*,
+    sol_chess::{ColorPreference, CustomError, GameConfig, GameState},
+    solana_sdk::signature::Signer,
+};
+
+fn queue_config() -> GameConfig {
+    GameConfig {
+        color: ColorPreference::Random,
+        ..blitz_config(Some(WAGER))
+    }
+}
+
+#[tokio::test]
+async fn queues_need_random_colors() {
+    let mut env = TestEnv::new(1, queue_config().get_time_category()).await;
+    let payer = env.player(0);
+
+    assert_program_error(
+        env.initialize_matchmaking_queue(&payer, blitz_config(Some(WAGER)))
+            .await,
+        CustomError::InvalidQueueConfig,
+    );
+    env.initialize_matchmaking_queue(&payer, queue_config())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn queueing_escrows_the_wager_until_dequeued() {
+    let game_config = queue_config();
+    let mut env = TestEnv::new(1, game_config.get_time_category()).await;
+    let player = env.player(0);
+    env.initialize_matchmaking_queue(&player, game_config)
+        .await
+        .unwrap();
+
+    assert_program_error(
+        env.enqueue(&player, &game_config).await,
+        CustomError::InsufficientBalance,
+    );
+    env.deposit(&player, WAGER).await;
+    env.enqueue(&player, &game_config).await.unwrap();
+    assert_eq!(env.get_user(player.pubkey()).await.balance, 0);
+    assert_program_error(
+        env.enqueue(&player, &game_config).await,
+        CustomError::AlreadyInQueue,
+    );
+
+    env.dequeue(&player, &game_config).await.unwrap();
+    assert_eq!(env.get_user(player.pubkey()).await.balance, WAGER);
+    assert!(env
+        .get_matchmaking_queue(&game_config)
+        .await
+        .get_entries()
+        .is_empty());
+    assert_program_error(
+        env.dequeue(&player, &game_config).await,
+        CustomError::NotInQueue,
+    );
+}
+
+#[tokio::test]
+async fn matching_starts_a_game_with_the_longest_waiting_player_as_white() {
+    let game_config = queue_config();
+    let mut env = TestEnv::new(3, game_config.get_time_category()).await;
+    let (first, second, cranker) = (env.player(0), env.player(1), env.player(2));
+    env.initialize_matchmaking_queue(&cranker, game_config)
+        .await
+        .unwrap();
+    env.deposit(&first, WAGER).await;
+    env.deposit(&second, WAGER).await;
+    env.enqueue(&first, &game_config).await.unwrap();
+    assert_program_error(
+        env.match_players(&cranker, &game_config, &first, &second)
+            .await
+            .1,
+        CustomError::NotInQueue,
+    );
+    env.warp(5).await;
+    env.enqueue(&second, &game_config).await.unwrap();
+
+    let (game, result) = env
+        .match_players(&cranker, &game_config, &second, &first)
+        .await;
+    result.unwrap();
+
+    let game_account = env.get_game(game).await;
+    assert_eq!(game_account.game_state, GameState::White);
+    assert_eq!(game_account.white, Some(get_user_key(first.pubkey())));
+    assert_eq!(game_account.black, Some(get_user_key(second.pubkey())));
+    assert_eq!(game_account.game_config, game_config);
+    for player in [&first, &second] {
+        let user = env.get_user(player.pubkey()).await;
+        assert_eq!(user.balance, 0);
+        assert_eq!(user.get_active_games(), vec![game]);
+    }
+    assert!(env
+        .get_matchmaking_queue(&game_config)
+        .await
+        .get_entries()
+        .is_empty());
+
+    env.play(&first, game, "e2e4").await.unwrap();
+}
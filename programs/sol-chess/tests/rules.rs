@@ -0,0 +1,122 @@
+//! Property tests of the move rules, driving random games through the same
+//! calls `move_piece` makes on chain.
+
+use {
+    anchor_lang::prelude::Pubkey,
+    proptest::prelude::*,
+    sol_chess::{Color, CustomError, Game, GameConfig, Piece, Square},
+};
+
+fn start_game() -> Game {
+    let mut game = Game::create(GameConfig::default(), 0, Pubkey::default(), 0, 0);
+    game.join_game(Pubkey::new_unique(), Color::White);
+    game.join_game(Pubkey::new_unique(), Color::Black);
+    game.start_game(0);
+    game
+}
+
+fn count_pieces(game: &Game, piece: Piece) -> usize {
+    game.board
+        .board
+        .iter()
+        .flatten()
+        .filter(|other| **other == piece)
+        .count()
+}
+
+// plays the legal move picked by each choice until the game runs out of
+// choices or of legal moves, checking the rules after every move
+fn play(game: &mut Game, choices: &[usize]) -> Result<(), TestCaseError> {
+    for choice in choices {
+        let color = game.get_current_player_color().unwrap();
+        let legal_moves = game.get_legal_moves(color);
+        if legal_moves.is_empty() {
+            // checkmate, or stalemate when not in check
+            prop_assert_eq!(
+                game.in_checkmate(color).unwrap(),
+                game.in_check(color).unwrap()
+            );
+            return Ok(());
+        }
+
+        let (from, to) = legal_moves[choice % legal_moves.len()];
+        let piece = game.board.get_piece(from);
+        prop_assert!(piece.is_color(color));
+        prop_assert!(game.is_valid_move(color, from, to));
+
+        let ply = game.ply;
+        game.move_piece(color, from, to);
+        prop_assert!(game.not_in_check(color).unwrap());
+        game.next_turn().unwrap();
+
+        prop_assert_eq!(game.ply, ply + 1);
+        prop_assert_eq!(game.get_current_player_color(), Ok(color.get_opposite()));
+        prop_assert_eq!(count_pieces(game, Piece::WhiteKing), 1);
+        prop_assert_eq!(count_pieces(game, Piece::BlackKing), 1);
+    }
+    Ok(())
+}
+
+fn square() -> impl Strategy<Value = Square> {
+    (0..8u8, 0..8u8).prop_map(|(rank, file)| Square { rank, file })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn legal_moves_never_leave_the_mover_in_check(
+        choices in prop::collection::vec(any::<usize>(), 0..160),
+    ) {
+        play(&mut start_game(), &choices)?;
+    }
+
+    #[test]
+    fn valid_moves_are_the_legal_moves_plus_self_checks(
+        choices in prop::collection::vec(any::<usize>(), 0..80),
+        moves in prop::collection::vec((square(), square()), 1..32),
+    ) {
+        let mut game = start_game();
+        play(&mut game, &choices)?;
+        prop_assume!(game.is_still_going());
+
+        let color = game.get_current_player_color().unwrap();
+        let legal_moves = game.get_legal_moves(color);
+        for (from, to) in moves {
+            let mut next = game.clone();
+            let is_legal = game.is_valid_move(color, from, to) && {
+                next.move_piece(color, from, to);
+                next.not_in_check(color).unwrap()
+            };
+            prop_assert_eq!(is_legal, legal_moves.contains(&(from, to)));
+        }
+    }
+}
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: CustomError) {
+    assert_eq!(
+        result.unwrap_err(),
+        anchor_lang::error::Error::from(expected)
+    );
+}
+
+#[test]
+fn games_not_in_progress_return_errors() {
+    let mut game = Game::create(GameConfig::default(), 0, Pubkey::default(), 0, 0);
+    game.join_game(Pubkey::new_unique(), Color::White);
+
+    assert_error(
+        game.get_current_player_color(),
+        CustomError::InvalidGameState,
+    );
+    assert_error(
+        game.get_current_player_pubkey(),
+        CustomError::InvalidGameState,
+    );
+    assert_error(game.next_turn(), CustomError::InvalidGameState);
+    assert_error(
+        game.get_adversary_player(Color::White),
+        CustomError::EmptySeat,
+    );
+    assert_error(Piece::Empty.get_color(), CustomError::EmptySquare);
+}
@@ -0,0 +1,80 @@
+use {
+    crate::*,
+    anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator},
+    sol_chess::{
+        Game, GameConfig, Leaderboard, LobbyPage, MatchmakingQueue, MoveLog, Seek, TimeCategory,
+        User,
+    },
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+};
+
+/// Decodes the data of a program account, discriminator included.
+pub fn decode_account<T: AccountDeserialize>(address: Pubkey, data: &[u8]) -> SdkResult<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| SdkError::AccountNotDeserializable(address))
+}
+
+pub fn fetch_account<T: AccountDeserialize>(rpc: &RpcClient, address: Pubkey) -> SdkResult<T> {
+    decode_account(address, &rpc.get_account_data(&address)?)
+}
+
+/// User account of `authority`.
+pub fn fetch_user(rpc: &RpcClient, authority: Pubkey) -> SdkResult<User> {
+    fetch_account(rpc, pda::user(authority))
+}
+
+pub fn fetch_game(rpc: &RpcClient, game: Pubkey) -> SdkResult<Game> {
+    fetch_account(rpc, game)
+}
+
+/// Move log of `game`.
+pub fn fetch_move_log(rpc: &RpcClient, game: Pubkey) -> SdkResult<MoveLog> {
+    fetch_account(rpc, pda::move_log(game))
+}
+
+pub fn fetch_leaderboard(rpc: &RpcClient, category: TimeCategory) -> SdkResult<Leaderboard> {
+    fetch_account(rpc, pda::leaderboard(category))
+}
+
+pub fn fetch_lobby_page(rpc: &RpcClient, page: u32) -> SdkResult<LobbyPage> {
+    fetch_account(rpc, pda::lobby_page(page))
+}
+
+/// Open games of every lobby page matching `filter`.
+pub fn fetch_seeks(rpc: &RpcClient, filter: &SeekFilter) -> SdkResult<Vec<Seek>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            LobbyPage::discriminator().to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: None,
+    };
+
+    let mut seeks = vec![];
+    for (address, account) in rpc.get_program_accounts_with_config(&sol_chess::ID, config)? {
+        let lobby_page = decode_account::<LobbyPage>(address, &account.data)?;
+        seeks.extend(
+            lobby_page
+                .get_seeks()
+                .iter()
+                .filter(|seek| filter.matches(seek)),
+        );
+    }
+
+    Ok(seeks)
+}
+
+pub fn fetch_matchmaking_queue(
+    rpc: &RpcClient,
+    game_config: &GameConfig,
+) -> SdkResult<MatchmakingQueue> {
+    fetch_account(rpc, pda::matchmaking_queue(game_config))
+}
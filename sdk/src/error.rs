@@ -0,0 +1,76 @@
+use {
+    anchor_lang::{error::ERROR_CODE_OFFSET, prelude::Pubkey},
+    sol_chess::CustomError,
+    solana_client::client_error::ClientError,
+    solana_sdk::{instruction::InstructionError, transaction::TransactionError},
+    std::fmt,
+};
+
+#[derive(Debug)]
+pub enum SdkError {
+    /// The program rejected the instruction with one of its own errors.
+    Program(CustomError),
+    /// Anchor rejected the instruction, usually an account constraint.
+    Anchor(u32),
+    /// The transaction failed for a reason outside the program.
+    Transaction(TransactionError),
+    /// The request never reached the program.
+    Rpc(Box<ClientError>),
+    AccountNotDeserializable(Pubkey),
+    NotInGame(Pubkey),
+    NoAdversary(Pubkey),
+}
+
+pub type SdkResult<T> = std::result::Result<T, SdkError>;
+
+impl SdkError {
+    /// The program error behind this error, if the program returned one.
+    pub fn get_custom_error(&self) -> Option<CustomError> {
+        match self {
+            SdkError::Program(error) => Some(*error),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransactionError> for SdkError {
+    fn from(error: TransactionError) -> Self {
+        match error {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                match CustomError::from_code(code) {
+                    Some(custom_error) => SdkError::Program(custom_error),
+                    None if code < ERROR_CODE_OFFSET => SdkError::Anchor(code),
+                    None => SdkError::Transaction(error),
+                }
+            }
+            error => SdkError::Transaction(error),
+        }
+    }
+}
+
+impl From<ClientError> for SdkError {
+    fn from(error: ClientError) -> Self {
+        match error.get_transaction_error() {
+            Some(transaction_error) => transaction_error.into(),
+            None => SdkError::Rpc(Box::new(error)),
+        }
+    }
+}
+
+impl fmt::Display for SdkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SdkError::Program(error) => write!(f, "{} ({})", error, error.name()),
+            SdkError::Anchor(code) => write!(f, "anchor error {}", code),
+            SdkError::Transaction(error) => write!(f, "transaction failed: {}", error),
+            SdkError::Rpc(error) => write!(f, "rpc error: {}", error),
+            SdkError::AccountNotDeserializable(address) => {
+                write!(f, "account {} could not be deserialized", address)
+            }
+            SdkError::NotInGame(game) => write!(f, "not playing game {}", game),
+            SdkError::NoAdversary(game) => write!(f, "game {} has no adversary yet", game),
+        }
+    }
+}
+
+impl std::error::Error for SdkError {}
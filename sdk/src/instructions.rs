@@ -0,0 +1,456 @@
+//! One builder per program instruction. `authority` is the wallet that signs
+//! and pays, its user account and every other PDA are derived from it.
+
+use {
+    crate::*,
+    anchor_lang::{prelude::Pubkey, InstructionData, ToAccountMetas},
+    sol_chess::{
+        accounts, instruction, Game, GameConfig, Invite, RatingRange, Square, TimeCategory,
+    },
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        system_program,
+        sysvar::{clock, slot_hashes},
+    },
+};
+
+// the metas come from the program's own account structs, so they follow any
+// change to a context's field order
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: sol_chess::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Appends the leaderboard of the game's time category to a settling
+/// instruction, only rated games need it.
+fn with_leaderboard(mut instruction: Instruction, game_account: &Game) -> Instruction {
+    if game_account.is_rated() {
+        instruction.accounts.push(AccountMeta::new(
+            pda::leaderboard(game_account.get_time_category()),
+            false,
+        ));
+    }
+    instruction
+}
+
+/// User accounts of `authority` and of their adversary in `game_account`.
+pub fn get_players(
+    authority: Pubkey,
+    game: Pubkey,
+    game_account: &Game,
+) -> SdkResult<(Pubkey, Pubkey)> {
+    let user = pda::user(authority);
+    let color = game_account
+        .get_player_color(user)
+        .map_err(|_| SdkError::NotInGame(game))?;
+    let adversary = if color.is_white() {
+        game_account.black
+    } else {
+        game_account.white
+    };
+    Ok((user, adversary.ok_or(SdkError::NoAdversary(game))?))
+}
+
+pub fn initialize_user(authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeUser {
+            payer: authority,
+            user: pda::user(authority),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeUser {},
+    )
+}
+
+/// Creates the `game_id`th game of `authority`, `game_id` being the
+/// `games_created` of their user account.
+pub fn initialize_game(
+    authority: Pubkey,
+    game_id: u64,
+    lobby_page: u32,
+    game_config: GameConfig,
+    rating_range: Option<RatingRange>,
+    invite: Option<Invite>,
+) -> Instruction {
+    let user = pda::user(authority);
+    let game = pda::game(user, game_id);
+    build(
+        accounts::InitializeGame {
+            payer: authority,
+            user,
+            game,
+            move_log: pda::move_log(game),
+            lobby_page: pda::lobby_page(lobby_page),
+            system_program: system_program::ID,
+            clock: clock::ID,
+        },
+        instruction::InitializeGame {
+            game_config,
+            rating_range,
+            invite,
+        },
+    )
+}
+
+pub fn initialize_leaderboard(authority: Pubkey, category: TimeCategory) -> Instruction {
+    build(
+        accounts::InitializeLeaderboard {
+            payer: authority,
+            leaderboard: pda::leaderboard(category),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeLeaderboard { category },
+    )
+}
+
+pub fn initialize_matchmaking_queue(authority: Pubkey, game_config: GameConfig) -> Instruction {
+    build(
+        accounts::InitializeMatchmakingQueue {
+            payer: authority,
+            matchmaking_queue: pda::matchmaking_queue(&game_config),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeMatchmakingQueue { game_config },
+    )
+}
+
+pub fn initialize_lobby_page(authority: Pubkey, page: u32) -> Instruction {
+    build(
+        accounts::InitializeLobbyPage {
+            payer: authority,
+            lobby_page: pda::lobby_page(page),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeLobbyPage { page },
+    )
+}
+
+pub fn join_game(
+    authority: Pubkey,
+    game: Pubkey,
+    game_account: &Game,
+    invite_code: Option<String>,
+) -> Instruction {
+    build(
+        accounts::JoinGame {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+            lobby_page: pda::lobby_page(game_account.lobby_page),
+            recent_slothashes: slot_hashes::ID,
+            clock: clock::ID,
+        },
+        instruction::JoinGame { invite_code },
+    )
+}
+
+pub fn leave_game(authority: Pubkey, game: Pubkey, game_account: &Game) -> Instruction {
+    build(
+        accounts::LeaveGame {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+            lobby_page: pda::lobby_page(game_account.lobby_page),
+        },
+        instruction::LeaveGame {},
+    )
+}
+
+pub fn cancel_game(authority: Pubkey, game: Pubkey, game_account: &Game) -> Instruction {
+    build(
+        accounts::CancelGame {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+            move_log: pda::move_log(game),
+            lobby_page: pda::lobby_page(game_account.lobby_page),
+        },
+        instruction::CancelGame {},
+    )
+}
+
+pub fn move_piece(
+    authority: Pubkey,
+    game: Pubkey,
+    game_account: &Game,
+    from: Square,
+    to: Square,
+) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    Ok(with_leaderboard(
+        build(
+            accounts::MovePiece {
+                payer: authority,
+                user,
+                adversary_user,
+                game,
+                move_log: pda::move_log(game),
+                system_program: system_program::ID,
+                clock: clock::ID,
+            },
+            instruction::MovePiece { from, to },
+        ),
+        game_account,
+    ))
+}
+
+pub fn deposit(authority: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::Deposit {
+            payer: authority,
+            user: pda::user(authority),
+            vault: pda::vault(),
+            system_program: system_program::ID,
+        },
+        instruction::Deposit { amount },
+    )
+}
+
+pub fn withdraw(authority: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::Withdraw {
+            payer: authority,
+            user: pda::user(authority),
+            vault: pda::vault(),
+            system_program: system_program::ID,
+        },
+        instruction::Withdraw { amount },
+    )
+}
+
+pub fn resign(authority: Pubkey, game: Pubkey, game_account: &Game) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    Ok(with_leaderboard(
+        build(
+            accounts::Resign {
+                payer: authority,
+                user,
+                adversary_user,
+                game,
+                clock: clock::ID,
+            },
+            instruction::Resign {},
+        ),
+        game_account,
+    ))
+}
+
+pub fn offer_draw(authority: Pubkey, game: Pubkey, game_account: &Game) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    Ok(with_leaderboard(
+        build(
+            accounts::OfferDraw {
+                payer: authority,
+                user,
+                adversary_user,
+                game,
+                clock: clock::ID,
+            },
+            instruction::OfferDraw {},
+        ),
+        game_account,
+    ))
+}
+
+pub fn claim_abandonment(
+    authority: Pubkey,
+    game: Pubkey,
+    game_account: &Game,
+    claim_win: bool,
+) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    Ok(with_leaderboard(
+        build(
+            accounts::ClaimAbandonment {
+                payer: authority,
+                user,
+                adversary_user,
+                game,
+                clock: clock::ID,
+            },
+            instruction::ClaimAbandonment { claim_win },
+        ),
+        game_account,
+    ))
+}
+
+/// Settles `game` if the player to move has run out of time, `authority` only
+/// pays for the transaction.
+pub fn check_timer(authority: Pubkey, game: Pubkey, game_account: &Game) -> SdkResult<Instruction> {
+    let (user, adversary_user) = if game_account.game_state.is_white_turn() {
+        (game_account.white, game_account.black)
+    } else {
+        (game_account.black, game_account.white)
+    };
+    Ok(with_leaderboard(
+        build(
+            accounts::CheckTimer {
+                payer: authority,
+                user: user.ok_or(SdkError::NoAdversary(game))?,
+                adversary_user: adversary_user.ok_or(SdkError::NoAdversary(game))?,
+                game,
+                clock: clock::ID,
+            },
+            instruction::CheckTimer {},
+        ),
+        game_account,
+    ))
+}
+
+pub fn abort(authority: Pubkey, game: Pubkey, game_account: &Game) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    Ok(build(
+        accounts::Abort {
+            payer: authority,
+            user,
+            adversary_user,
+            game,
+            clock: clock::ID,
+        },
+        instruction::Abort {},
+    ))
+}
+
+pub fn decline_draw(authority: Pubkey, game: Pubkey) -> Instruction {
+    build(
+        accounts::DeclineDraw {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+        },
+        instruction::DeclineDraw {},
+    )
+}
+
+pub fn request_takeback(authority: Pubkey, game: Pubkey) -> Instruction {
+    build(
+        accounts::RequestTakeback {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+        },
+        instruction::RequestTakeback {},
+    )
+}
+
+pub fn decline_takeback(authority: Pubkey, game: Pubkey) -> Instruction {
+    build(
+        accounts::DeclineTakeback {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+        },
+        instruction::DeclineTakeback {},
+    )
+}
+
+pub fn offer_rematch(authority: Pubkey, game: Pubkey) -> Instruction {
+    build(
+        accounts::OfferRematch {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+        },
+        instruction::OfferRematch {},
+    )
+}
+
+pub fn accept_takeback(authority: Pubkey, game: Pubkey) -> Instruction {
+    build(
+        accounts::AcceptTakeback {
+            payer: authority,
+            user: pda::user(authority),
+            game,
+            move_log: pda::move_log(game),
+            system_program: system_program::ID,
+            clock: clock::ID,
+        },
+        instruction::AcceptTakeback {},
+    )
+}
+
+/// Accepts the rematch offer of `game`, the rematch being the `game_id`th game
+/// of `authority`.
+pub fn accept_rematch(
+    authority: Pubkey,
+    game: Pubkey,
+    game_account: &Game,
+    game_id: u64,
+) -> SdkResult<Instruction> {
+    let (user, adversary_user) = get_players(authority, game, game_account)?;
+    let rematch_game = pda::game(user, game_id);
+    Ok(build(
+        accounts::AcceptRematch {
+            payer: authority,
+            user,
+            adversary_user,
+            game,
+            rematch_game,
+            move_log: pda::move_log(rematch_game),
+            system_program: system_program::ID,
+            clock: clock::ID,
+        },
+        instruction::AcceptRematch {},
+    ))
+}
+
+pub fn set_max_active_games(authority: Pubkey, max_active_games: u8) -> Instruction {
+    build(
+        accounts::SetMaxActiveGames {
+            payer: authority,
+            user: pda::user(authority),
+        },
+        instruction::SetMaxActiveGames { max_active_games },
+    )
+}
+
+pub fn enqueue(authority: Pubkey, game_config: &GameConfig) -> Instruction {
+    build(
+        accounts::Enqueue {
+            payer: authority,
+            user: pda::user(authority),
+            matchmaking_queue: pda::matchmaking_queue(game_config),
+            clock: clock::ID,
+        },
+        instruction::Enqueue {},
+    )
+}
+
+pub fn dequeue(authority: Pubkey, game_config: &GameConfig) -> Instruction {
+    build(
+        accounts::Dequeue {
+            payer: authority,
+            user: pda::user(authority),
+            matchmaking_queue: pda::matchmaking_queue(game_config),
+        },
+        instruction::Dequeue {},
+    )
+}
+
+/// Pairs two queued users, anyone can crank it. The game is the `game_id`th
+/// game of `user`, a user account.
+pub fn match_players(
+    payer: Pubkey,
+    user: Pubkey,
+    adversary_user: Pubkey,
+    game_config: &GameConfig,
+    game_id: u64,
+) -> Instruction {
+    let game = pda::game(user, game_id);
+    build(
+        accounts::MatchPlayers {
+            payer,
+            user,
+            adversary_user,
+            matchmaking_queue: pda::matchmaking_queue(game_config),
+            game,
+            move_log: pda::move_log(game),
+            system_program: system_program::ID,
+            clock: clock::ID,
+        },
+        instruction::MatchPlayers {},
+    )
+}
@@ -0,0 +1,17 @@
+//! Typed instruction builders, account fetchers and error handling for the
+//! sol-chess program, shared by the client, bots and services.
+
+pub use sol_chess;
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+pub mod seek_filter;
+pub mod transaction;
+
+pub use accounts::*;
+pub use error::*;
+pub use instructions::*;
+pub use seek_filter::*;
+pub use transaction::*;
@@ -0,0 +1,39 @@
+//! Addresses of the program accounts, derived the same way the program checks
+//! them.
+
+use {
+    anchor_lang::prelude::Pubkey,
+    sol_chess::{GameConfig, TimeCategory},
+};
+
+pub const SEED_VAULT: &[u8] = b"vault";
+
+pub fn user(authority: Pubkey) -> Pubkey {
+    sol_chess::User::pda(authority).0
+}
+
+/// Game created by `user`, the user account and not its authority, as its
+/// `game_id`th game.
+pub fn game(user: Pubkey, game_id: u64) -> Pubkey {
+    sol_chess::Game::pda(user, game_id).0
+}
+
+pub fn vault() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_VAULT], &sol_chess::ID).0
+}
+
+pub fn move_log(game: Pubkey) -> Pubkey {
+    sol_chess::MoveLog::pda(game).0
+}
+
+pub fn leaderboard(category: TimeCategory) -> Pubkey {
+    sol_chess::Leaderboard::pda(category).0
+}
+
+pub fn lobby_page(page: u32) -> Pubkey {
+    sol_chess::LobbyPage::pda(page).0
+}
+
+pub fn matchmaking_queue(game_config: &GameConfig) -> Pubkey {
+    sol_chess::MatchmakingQueue::pda(game_config).0
+}
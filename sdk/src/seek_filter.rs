@@ -0,0 +1,161 @@
+use sol_chess::{Color, Seek, TimeCategory};
+
+/// Criteria an open game of the lobby must meet, every unset one matches.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeekFilter {
+    pub category: Option<TimeCategory>,
+    pub is_rated: Option<bool>,
+    pub max_wager: Option<u64>,
+    /// Seat offered to the joiner, random seats match either color.
+    pub color: Option<Color>,
+    /// Rating of the joiner, checked against the seek's rating range.
+    pub elo: Option<u32>,
+}
+
+impl SeekFilter {
+    pub fn matches(&self, seek: &Seek) -> bool {
+        if let Some(category) = self.category {
+            if seek.game_config.get_time_category() != category {
+                return false;
+            }
+        }
+
+        if let Some(is_rated) = self.is_rated {
+            if seek.game_config.is_rated() != is_rated {
+                return false;
+            }
+        }
+
+        if let Some(max_wager) = self.max_wager {
+            if seek.game_config.get_wager_or_zero() > max_wager {
+                return false;
+            }
+        }
+
+        if let (Some(color), Some(seek_color)) = (self.color, seek.color) {
+            if color != seek_color {
+                return false;
+            }
+        }
+
+        if let (Some(elo), Some(rating_range)) = (self.elo, seek.rating_range) {
+            if !rating_range.contains(elo) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anchor_lang::prelude::Pubkey,
+        sol_chess::{GameConfig, RatingRange},
+    };
+
+    // a rated 5+0 blitz seek offering black for 100 lamports
+    fn seek() -> Seek {
+        Seek {
+            game: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            game_config: GameConfig {
+                timer: 300,
+                is_rated: true,
+                wager: Some(100),
+                ..GameConfig::default()
+            },
+            color: Some(Color::Black),
+            rating_range: None,
+        }
+    }
+
+    #[test]
+    fn the_default_filter_matches_everything() {
+        assert!(SeekFilter::default().matches(&seek()));
+    }
+
+    #[test]
+    fn filters_by_category_and_rated() {
+        let blitz = SeekFilter {
+            category: Some(TimeCategory::Blitz),
+            ..SeekFilter::default()
+        };
+        let rapid = SeekFilter {
+            category: Some(TimeCategory::Rapid),
+            ..SeekFilter::default()
+        };
+        assert!(blitz.matches(&seek()));
+        assert!(!rapid.matches(&seek()));
+
+        let unrated = SeekFilter {
+            is_rated: Some(false),
+            ..SeekFilter::default()
+        };
+        assert!(!unrated.matches(&seek()));
+        assert!(SeekFilter {
+            is_rated: Some(true),
+            ..blitz
+        }
+        .matches(&seek()));
+    }
+
+    #[test]
+    fn filters_by_max_wager() {
+        let filter = |max_wager| SeekFilter {
+            max_wager: Some(max_wager),
+            ..SeekFilter::default()
+        };
+        assert!(filter(100).matches(&seek()));
+        assert!(!filter(99).matches(&seek()));
+
+        let free = Seek {
+            game_config: GameConfig {
+                wager: None,
+                ..seek().game_config
+            },
+            ..seek()
+        };
+        assert!(filter(0).matches(&free));
+    }
+
+    #[test]
+    fn random_seats_match_either_color() {
+        let filter = |color| SeekFilter {
+            color: Some(color),
+            ..SeekFilter::default()
+        };
+        assert!(filter(Color::Black).matches(&seek()));
+        assert!(!filter(Color::White).matches(&seek()));
+
+        let random = Seek {
+            color: None,
+            ..seek()
+        };
+        assert!(filter(Color::White).matches(&random));
+        assert!(filter(Color::Black).matches(&random));
+    }
+
+    #[test]
+    fn filters_by_the_rating_range() {
+        let filter = |elo| SeekFilter {
+            elo: Some(elo),
+            ..SeekFilter::default()
+        };
+        let ranged = Seek {
+            rating_range: Some(RatingRange {
+                min: 1400,
+                max: 1600,
+            }),
+            ..seek()
+        };
+        assert!(filter(1400).matches(&ranged));
+        assert!(filter(1600).matches(&ranged));
+        assert!(!filter(1399).matches(&ranged));
+        assert!(!filter(1601).matches(&ranged));
+        // open to every rating
+        assert!(filter(800).matches(&seek()));
+    }
+}
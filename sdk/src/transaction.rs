@@ -0,0 +1,30 @@
+use {
+    crate::*,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signature},
+        signer::Signer,
+        transaction::Transaction,
+    },
+};
+
+/// Sends the instructions in a single transaction paid by `payer` and waits
+/// for confirmation, program failures come back as `SdkError::Program`.
+pub fn send_and_confirm(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> SdkResult<Signature> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        rpc.get_latest_blockhash()?,
+    );
+    Ok(rpc.send_and_confirm_transaction(&transaction)?)
+}